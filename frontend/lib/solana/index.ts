@@ -39,6 +39,15 @@ function encodeString(value: string): Buffer {
   return Buffer.concat([len, bytes]);
 }
 
+export type NftAttribute = { key: string; value: string };
+
+// Vec<NftAttribute> as u32 length followed by (key, value) string pairs
+function encodeAttributes(attributes: NftAttribute[]): Buffer {
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(attributes.length, 0);
+  return Buffer.concat([len, ...attributes.flatMap((a) => [encodeString(a.key), encodeString(a.value)])]);
+}
+
 // PDA helpers matching seeds in Rust
 export function findConnectionPda(programId: PublicKey, chainId: bigint) {
   return PublicKey.findProgramAddressSync([
@@ -69,6 +78,10 @@ export function findNftOriginPda(programId: PublicKey, mint: PublicKey, tokenId:
   ], programId);
 }
 
+export function findEventAuthorityPda(programId: PublicKey) {
+  return PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], programId);
+}
+
 export function findMetadataPda(mint: PublicKey) {
   return PublicKey.findProgramAddressSync([
     Buffer.from("metadata"),
@@ -102,6 +115,7 @@ export type ProgramState = {
   nextTokenId: bigint;
   totalSupply: bigint;
   isInitialized: boolean;
+  version: number;
 };
 
 function readString(buf: Buffer, offset: number): { value: string; next: number } {
@@ -124,7 +138,8 @@ export function decodeProgramState(data: Buffer): ProgramState {
   const nextTokenId = data.readBigUInt64LE(o); o += 8;
   const totalSupply = data.readBigUInt64LE(o); o += 8;
   const isInitialized = data[o] !== 0; o += 1;
-  return { authority, name, symbol, gateway, gasLimit, uniswapRouter, isPaused, nextTokenId, totalSupply, isInitialized };
+  const version = data[o]; o += 1;
+  return { authority, name, symbol, gateway, gasLimit, uniswapRouter, isPaused, nextTokenId, totalSupply, isInitialized, version };
 }
 
 export function decodeEnumerableData(data: Buffer): { tokenId: bigint; owner: PublicKey; indexInOwnerList: bigint } {
@@ -143,6 +158,15 @@ export class UniversalNftClient {
     this.programId = programId;
   }
 
+  // Accounts appended by #[event_cpi] for instructions that emit events via emit_cpi!
+  private eventCpiKeys() {
+    const [eventAuthority] = findEventAuthorityPda(this.programId);
+    return [
+      { pubkey: eventAuthority, isSigner: false, isWritable: false },
+      { pubkey: this.programId, isSigner: false, isWritable: false },
+    ];
+  }
+
   // initialize(name: string, symbol: string, gateway: PublicKey, gasLimit: bigint, uniswapRouter: PublicKey)
   buildInitializeIx(args: { state: PublicKey; initialOwner: PublicKey; name: string; symbol: string; gateway: PublicKey; gasLimit: bigint | number; uniswapRouter: PublicKey; }): TransactionInstruction {
     const data = Buffer.concat([
@@ -158,6 +182,7 @@ export class UniversalNftClient {
       { pubkey: args.state, isSigner: true, isWritable: true },
       { pubkey: args.initialOwner, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
@@ -170,6 +195,7 @@ export class UniversalNftClient {
     const keys = [
       { pubkey: args.state, isSigner: false, isWritable: true },
       { pubkey: args.authority, isSigner: true, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
@@ -182,6 +208,7 @@ export class UniversalNftClient {
     const keys = [
       { pubkey: args.state, isSigner: false, isWritable: true },
       { pubkey: args.authority, isSigner: true, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
@@ -197,6 +224,7 @@ export class UniversalNftClient {
       { pubkey: args.connectionPda, isSigner: false, isWritable: true },
       { pubkey: args.authority, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
@@ -206,6 +234,7 @@ export class UniversalNftClient {
     const keys = [
       { pubkey: args.state, isSigner: false, isWritable: true },
       { pubkey: args.authority, isSigner: true, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
@@ -215,6 +244,7 @@ export class UniversalNftClient {
     const keys = [
       { pubkey: args.state, isSigner: false, isWritable: true },
       { pubkey: args.authority, isSigner: true, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
@@ -233,10 +263,12 @@ export class UniversalNftClient {
     authority: PublicKey; // state.authority
     mintAuthority: PublicKey;
     uri: string;
+    attributes?: NftAttribute[];
   }): TransactionInstruction {
     const data = Buffer.concat([
       anchorDiscriminator("safe_mint"),
       encodeString(args.uri),
+      encodeAttributes(args.attributes ?? []),
     ]);
     const keys = [
       { pubkey: args.state, isSigner: false, isWritable: true },
@@ -256,6 +288,7 @@ export class UniversalNftClient {
       { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
@@ -272,6 +305,7 @@ export class UniversalNftClient {
       { pubkey: args.tokenAccount, isSigner: false, isWritable: true },
       { pubkey: args.authority, isSigner: true, isWritable: false },
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
@@ -291,15 +325,17 @@ export class UniversalNftClient {
       { pubkey: args.nftOriginPda, isSigner: false, isWritable: false },
       { pubkey: args.owner, isSigner: true, isWritable: false },
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
 
-  buildHandleCrossChainReceiveIx(args: { state: PublicKey; nftDataPda: PublicKey; enumerablePda: PublicKey; mint: PublicKey; tokenAccount: PublicKey; recipient: PublicKey; payer: PublicKey; mintAuthority: PublicKey; tokenId: bigint; uri: string; senderChainId: bigint }): TransactionInstruction {
+  buildHandleCrossChainReceiveIx(args: { state: PublicKey; nftDataPda: PublicKey; enumerablePda: PublicKey; mint: PublicKey; tokenAccount: PublicKey; recipient: PublicKey; payer: PublicKey; mintAuthority: PublicKey; tokenId: bigint; uri: string; attributes?: NftAttribute[]; senderChainId: bigint }): TransactionInstruction {
     const data = Buffer.concat([
       anchorDiscriminator("handle_cross_chain_receive"),
       encodeU64(args.tokenId),
       encodeString(args.uri),
+      encodeAttributes(args.attributes ?? []),
       encodeU64(args.senderChainId),
       encodeString("") // _original_sender not used by program logic
     ]);
@@ -315,6 +351,7 @@ export class UniversalNftClient {
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
@@ -336,6 +373,7 @@ export class UniversalNftClient {
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ...this.eventCpiKeys(),
     ];
     return new TransactionInstruction({ programId: this.programId, keys, data });
   }
@@ -377,6 +415,7 @@ export async function buildSafeMintTransaction(opts: {
   mintAuthority: PublicKey;
   recipient: PublicKey;
   uri: string;
+  attributes?: NftAttribute[];
 }): Promise<{ instruction: TransactionInstruction; signers: Keypair[]; addresses: { mint: PublicKey; tokenAccount: PublicKey; nftDataPda: PublicKey; enumerablePda: PublicKey; metadata: PublicKey; masterEdition: PublicKey; nftOriginPda: PublicKey; nextTokenId: bigint } }> {
   const programId = opts.programId ?? DEFAULT_PROGRAM_ID;
   const client = new UniversalNftClient(programId);
@@ -407,6 +446,7 @@ export async function buildSafeMintTransaction(opts: {
     authority: opts.authority,
     mintAuthority: opts.mintAuthority,
     uri: opts.uri,
+    attributes: opts.attributes,
   });
 
   return { instruction: ix, signers: [mintKp], addresses: { mint, tokenAccount, nftDataPda, enumerablePda, metadata, masterEdition, nftOriginPda, nextTokenId } };
@@ -455,7 +495,7 @@ export function buildTransferCrossChainTransaction(args: { programId?: PublicKey
   return { instruction: ix, tokenAccount, nftDataPda, nftOriginPda };
 }
 
-export function buildHandleReceiveTransaction(args: { programId?: PublicKey; state: PublicKey; payer: PublicKey; mintAuthority: PublicKey; recipient: PublicKey; tokenId: bigint; uri: string; senderChainId: bigint }): { instruction: TransactionInstruction; signers: Keypair[]; mint: PublicKey; tokenAccount: PublicKey; nftDataPda: PublicKey; enumerablePda: PublicKey } {
+export function buildHandleReceiveTransaction(args: { programId?: PublicKey; state: PublicKey; payer: PublicKey; mintAuthority: PublicKey; recipient: PublicKey; tokenId: bigint; uri: string; attributes?: NftAttribute[]; senderChainId: bigint }): { instruction: TransactionInstruction; signers: Keypair[]; mint: PublicKey; tokenAccount: PublicKey; nftDataPda: PublicKey; enumerablePda: PublicKey } {
   const programId = args.programId ?? DEFAULT_PROGRAM_ID;
   const client = new UniversalNftClient(programId);
   const [nftDataPda] = findNftDataPda(programId, args.tokenId);
@@ -463,7 +503,7 @@ export function buildHandleReceiveTransaction(args: { programId?: PublicKey; sta
   const mintKp = Keypair.generate();
   const mint = mintKp.publicKey;
   const tokenAccount = getAssociatedTokenAddressSync(mint, args.recipient);
-  const ix = client.buildHandleCrossChainReceiveIx({ state: args.state, nftDataPda, enumerablePda, mint, tokenAccount, recipient: args.recipient, payer: args.payer, mintAuthority: args.mintAuthority, tokenId: args.tokenId, uri: args.uri, senderChainId: args.senderChainId });
+  const ix = client.buildHandleCrossChainReceiveIx({ state: args.state, nftDataPda, enumerablePda, mint, tokenAccount, recipient: args.recipient, payer: args.payer, mintAuthority: args.mintAuthority, tokenId: args.tokenId, uri: args.uri, attributes: args.attributes, senderChainId: args.senderChainId });
   return { instruction: ix, signers: [mintKp], mint, tokenAccount, nftDataPda, enumerablePda };
 }
 
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{sysvar, sysvar::instructions::get_instruction_relative};
+use anchor_lang::solana_program::{program::set_return_data, sysvar};
 use std::mem::size_of;
 
 declare_id!("4xEw862A2SEwMjofPkUyd4NEekmVJKJsdHkK3UkAtDrc");
@@ -20,23 +20,11 @@ pub mod connected {
         data: Vec<u8>,
     ) -> Result<()> {
         // NOTE: this is an illustration how connected programs can check if caller is gateway
-        // it is up to connected programs to configure sysvar account in remaining_accounts
-        // same approach can be used on on_revert and SPL connected programs
-        let current_ix = get_instruction_relative(
-            0,
-            &ctx.accounts.instruction_sysvar_account.to_account_info(),
-        )
-        .unwrap();
-
+        // the gateway signs the call with its caller PDA for this program, checked by the `caller` constraints
+        // the gateway signs on_revert and SPL calls with the same PDA, so those contexts can add the same `caller` account
         msg!(
-            "on_call invoked by: {}, gateway is {}",
-            current_ix.program_id,
-            gateway::ID
-        );
-
-        require!(
-            current_ix.program_id == gateway::ID,
-            ErrorCode::InvalidCaller
+            "on_call invoked by gateway caller {}",
+            ctx.accounts.caller.key()
         );
 
         let pda = &mut ctx.accounts.pda;
@@ -68,6 +56,9 @@ pub mod connected {
             pda.last_message
         );
 
+        // NOTE: return data is emitted by the gateway in the ExecuteCompleted event
+        set_return_data(pda.last_message.as_bytes());
+
         Ok(())
     }
 
@@ -77,6 +68,7 @@ pub mod connected {
         sender: Pubkey,
         data: Vec<u8>,
     ) -> Result<()> {
+        // NOTE: this example does not check the caller; see on_call for the `caller` signer check
         let pda = &mut ctx.accounts.pda;
 
         // Store the sender's public key
@@ -118,6 +110,18 @@ pub mod connected {
             signer: ctx.accounts.signer.to_account_info(),
             pda: ctx.accounts.gateway_pda.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
+            fee_exemption: ctx
+                .accounts
+                .gateway_fee_exemption
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            instructions_sysvar: ctx
+                .accounts
+                .instruction_sysvar_account
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            event_authority: ctx.accounts.gateway_event_authority.to_account_info(),
+            program: gateway_program.clone(),
         };
 
         let cpi_program = gateway_program;
@@ -153,9 +157,8 @@ pub struct OnCall<'info> {
 
     pub system_program: Program<'info, System>,
 
-    /// CHECK: This is test program.
-    #[account(address = sysvar::instructions::id())]
-    instruction_sysvar_account: UncheckedAccount<'info>,
+    #[account(seeds = [b"caller", crate::ID.as_ref()], bump, seeds::program = gateway::ID)]
+    pub caller: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -181,6 +184,16 @@ pub struct TriggerDeposit<'info> {
     /// CHECK: Only used for CPI
     pub gateway_program: UncheckedAccount<'info>,
 
+    /// CHECK: Validated by the gateway program via seeds
+    pub gateway_event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the gateway program
+    pub gateway_fee_exemption: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: This is test program.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction_sysvar_account: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -199,7 +212,4 @@ pub enum ErrorCode {
 
     #[msg("Revert message detected. Transaction execution halted.")]
     RevertMessage,
-
-    #[msg("Caller is not the gateway program.")]
-    InvalidCaller,
 }
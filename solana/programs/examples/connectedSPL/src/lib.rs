@@ -19,6 +19,7 @@ pub mod connected_spl {
         sender: [u8; 20],
         data: Vec<u8>,
     ) -> Result<()> {
+        // NOTE: this example does not check the caller; see the connected example's on_call for the `caller` signer check
         let pda = &mut ctx.accounts.pda;
 
         // Store the sender's public key
@@ -70,6 +71,7 @@ pub mod connected_spl {
         sender: Pubkey,
         data: Vec<u8>,
     ) -> Result<()> {
+        // NOTE: this example does not check the caller; see the connected example's on_call for the `caller` signer check
         let pda = &mut ctx.accounts.pda;
 
         // Store the sender's public key
@@ -0,0 +1,269 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+declare_id!("FmtAdkuFHbhKrkGczDL61rDpJobKQtH8TPVgDfM7hbpt");
+
+// NOTE: this is just example AMM with a fixed exchange rate that gateway can CPI into in deposit_with_swap
+// and deposit_sol_with_swap for testing
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        rate_numerator: u64,
+        rate_denominator: u64,
+    ) -> Result<()> {
+        require!(rate_denominator > 0, ErrorCode::InvalidRate);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.input_mint = ctx.accounts.input_mint.key();
+        pool.output_mint = ctx.accounts.output_mint.key();
+        pool.rate_numerator = rate_numerator;
+        pool.rate_denominator = rate_denominator;
+
+        Ok(())
+    }
+
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Result<()> {
+        execute_swap(ctx.accounts, ctx.bumps.pool, amount_in, minimum_amount_out)
+    }
+
+    // swaps like `swap`, then deposits SOL into the gateway from within the swap,
+    // used to test that the gateway rejects swaps reentering it
+    pub fn swap_and_deposit(
+        ctx: Context<SwapAndDeposit>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        deposit_amount: u64,
+    ) -> Result<()> {
+        execute_swap(
+            &ctx.accounts.swap,
+            ctx.bumps.swap.pool,
+            amount_in,
+            minimum_amount_out,
+        )?;
+
+        let cpi_accounts = gateway::cpi::accounts::Deposit {
+            signer: ctx.accounts.swap.user.to_account_info(),
+            pda: ctx.accounts.gateway_pda.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            fee_exemption: None,
+            instructions_sysvar: None,
+            event_authority: ctx.accounts.gateway_event_authority.to_account_info(),
+            program: ctx.accounts.gateway_program.to_account_info(),
+        };
+        gateway::cpi::deposit(
+            CpiContext::new(ctx.accounts.gateway_program.to_account_info(), cpi_accounts),
+            deposit_amount,
+            [1u8; 20],
+            None,
+        )
+    }
+
+    // pays out lamports held by a pool initialized with the native mint as output mint
+    pub fn swap_to_sol(
+        ctx: Context<SwapToSol>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let amount_out = (amount_in as u128 * pool.rate_numerator as u128
+            / pool.rate_denominator as u128) as u64;
+        require!(
+            amount_out >= minimum_amount_out,
+            ErrorCode::SlippageExceeded
+        );
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_source.to_account_info(),
+                    mint: ctx.accounts.input_mint.to_account_info(),
+                    to: ctx.accounts.pool_input_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount_in,
+            ctx.accounts.input_mint.decimals,
+        )?;
+
+        ctx.accounts.pool.sub_lamports(amount_out)?;
+        ctx.accounts.destination.add_lamports(amount_out)?;
+
+        msg!("Swapped {} for {} lamports", amount_in, amount_out);
+
+        Ok(())
+    }
+}
+
+fn execute_swap(
+    accounts: &Swap,
+    pool_bump: u8,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let pool = &accounts.pool;
+    let amount_out =
+        (amount_in as u128 * pool.rate_numerator as u128 / pool.rate_denominator as u128) as u64;
+    require!(
+        amount_out >= minimum_amount_out,
+        ErrorCode::SlippageExceeded
+    );
+
+    let token = &accounts.token_program;
+    transfer_checked(
+        CpiContext::new(
+            token.to_account_info(),
+            TransferChecked {
+                from: accounts.user_source.to_account_info(),
+                mint: accounts.input_mint.to_account_info(),
+                to: accounts.pool_input_vault.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        ),
+        amount_in,
+        accounts.input_mint.decimals,
+    )?;
+
+    let input_mint = accounts.input_mint.key();
+    let output_mint = accounts.output_mint.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"pool",
+        input_mint.as_ref(),
+        output_mint.as_ref(),
+        &[pool_bump],
+    ]];
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token.to_account_info(),
+            TransferChecked {
+                from: accounts.pool_output_vault.to_account_info(),
+                mint: accounts.output_mint.to_account_info(),
+                to: accounts.user_destination.to_account_info(),
+                authority: accounts.pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount_out,
+        accounts.output_mint.decimals,
+    )?;
+
+    msg!("Swapped {} for {}", amount_in, amount_out);
+
+    Ok(())
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub rate_numerator: u64,
+    pub rate_denominator: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool", input_mint.key().as_ref(), output_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub input_mint: InterfaceAccount<'info, Mint>,
+
+    pub output_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"pool", input_mint.key().as_ref(), output_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub input_mint: InterfaceAccount<'info, Mint>,
+
+    pub output_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = input_mint)]
+    pub user_source: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = output_mint)]
+    pub user_destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = input_mint, token::authority = pool)]
+    pub pool_input_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = output_mint, token::authority = pool)]
+    pub pool_output_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SwapAndDeposit<'info> {
+    pub swap: Swap<'info>,
+
+    #[account(mut)]
+    /// CHECK: Validated by the gateway program via seeds
+    pub gateway_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the gateway program via seeds
+    pub gateway_event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Only used for CPI
+    pub gateway_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SwapToSol<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", input_mint.key().as_ref(), native_mint::ID.as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub input_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = input_mint)]
+    pub user_source: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = input_mint, token::authority = pool)]
+    pub pool_input_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: any account receiving the lamports
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("InvalidRate")]
+    InvalidRate,
+    #[msg("SlippageExceeded")]
+    SlippageExceeded,
+}
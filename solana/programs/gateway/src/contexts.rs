@@ -1,10 +1,12 @@
 use crate::state::*;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{Mint, Token, TokenAccount};
-use std::mem::size_of;
+use anchor_spl::token::{spl_token::native_mint, Token};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 /// Instruction context for initializing the program.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     /// The account of the signer initializing the program.
@@ -12,14 +14,32 @@ pub struct Initialize<'info> {
     pub signer: Signer<'info>,
 
     /// Gateway PDA.
-    #[account(init, payer = signer, space = size_of::<Pda>() + 8, seeds = [b"meta"], bump)]
+    #[account(init, payer = signer, space = 8 + Pda::INIT_SPACE, seeds = [b"meta"], bump)]
     pub pda: Account<'info, Pda>,
 
     /// The system program.
     pub system_program: Program<'info, System>,
 }
 
+/// Instruction context for migrating the gateway PDA to the current layout.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpgradePda<'info> {
+    /// The account of the authority performing the migration, paying any rent top-up.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    /// CHECK: Legacy layout is validated and deserialized during migration.
+    #[account(mut, owner = crate::ID, seeds = [b"meta"], bump)]
+    pub pda: UncheckedAccount<'info>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
 /// Instruction context for executing a call on program.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Execute<'info> {
     /// The account of the signer making the deposit.
@@ -30,6 +50,11 @@ pub struct Execute<'info> {
     #[account(mut, seeds = [b"meta"], bump)]
     pub pda: Account<'info, Pda>,
 
+    /// Rate limit PDA for SOL, uninitialized if SOL outbounds are not rate limited.
+    /// CHECK: Deserialized and updated only if initialized.
+    #[account(mut, seeds = [b"rate_limit", Pubkey::default().as_ref()], bump)]
+    pub rate_limit: UncheckedAccount<'info>,
+
     /// The destination program.
     /// CHECK: This is arbitrary program.
     pub destination_program: AccountInfo<'info>,
@@ -43,9 +68,25 @@ pub struct Execute<'info> {
         seeds::program = destination_program.key()
     )]
     pub destination_program_pda: UncheckedAccount<'info>,
+
+    /// Account list published by the destination program, checked against the forwarded accounts if it exists.
+    /// CHECK: Deserialized only if owned by the destination program.
+    #[account(
+        seeds = [b"expected_accounts"],
+        bump,
+        seeds::program = destination_program.key()
+    )]
+    pub expected_accounts: UncheckedAccount<'info>,
+
+    /// Gateway caller PDA of the destination program, signing the call when forwarded so the
+    /// destination program can authenticate the gateway.
+    /// CHECK: Holds no data, only used as a signer.
+    #[account(seeds = [b"caller", destination_program.key().as_ref()], bump)]
+    pub caller: UncheckedAccount<'info>,
 }
 
 /// Instruction context for increment nonce.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct IncrementNonce<'info> {
     /// The account of the signer incrementing nonce.
@@ -57,7 +98,53 @@ pub struct IncrementNonce<'info> {
     pub pda: Account<'info, Pda>,
 }
 
+/// Instruction context for increment nonce with a typed failure code.
+/// The refund accounts are only passed when the amount of the failed outbound is refunded.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct IncrementNonceV2<'info> {
+    /// The account of the signer incrementing nonce.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The revert or abort address of the failed outbound, required for refunds.
+    /// CHECK: Address is committed to by the message hash.
+    #[account(mut)]
+    pub refund_address: Option<UncheckedAccount<'info>>,
+
+    /// Rate limit PDA of the refunded asset, required for refunds.
+    /// CHECK: Address is validated during instruction processing, deserialized and updated only if initialized.
+    #[account(mut)]
+    pub rate_limit: Option<UncheckedAccount<'info>>,
+
+    /// The mint account of the refunded SPL token, required for SPL refunds.
+    pub mint_account: Option<InterfaceAccount<'info, Mint>>,
+
+    /// The associated token account for the Gateway PDA, required for SPL refunds.
+    #[account(mut)]
+    pub pda_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The refund address associated token account, required for SPL refunds.
+    /// CHECK: Validation will occur during instruction processing.
+    #[account(mut)]
+    pub refund_ata: Option<UncheckedAccount<'info>>,
+
+    /// The token program, required for SPL refunds.
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// The associated token program, required for SPL refunds.
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
 /// Instruction context for SOL deposit operations.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     /// The account of the signer making the deposit.
@@ -70,9 +157,74 @@ pub struct Deposit<'info> {
 
     /// The system program.
     pub system_program: Program<'info, System>,
+
+    /// Fee exemption entry of the program depositing through CPI, if any.
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    /// The instructions sysvar, used to identify the program depositing through CPI.
+    /// CHECK: Address is validated by the constraint.
+    #[account(address = sysvar::instructions::id())]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+/// Instruction context for multi-asset deposits.
+/// Each SPL token asset is described by `DEPOSIT_ASSET_ACCOUNTS` remaining accounts.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositMulti<'info> {
+    /// The account of the signer making the deposit.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+
+    /// Fee exemption entry of the program depositing through CPI, if any.
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    /// The instructions sysvar, used to identify the program depositing through CPI.
+    /// CHECK: Address is validated by the constraint.
+    #[account(address = sysvar::instructions::id())]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+/// Instruction context for depositing wrapped SOL, which is unwrapped and credited as SOL.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositWsol<'info> {
+    /// The account of the signer making the deposit, receiving the unwrapped SOL and the rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The wrapped SOL account of the signer, closed once unwrapped.
+    #[account(mut, token::authority = signer, token::token_program = token_program)]
+    pub wsol_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The SPL Token program.
+    pub token_program: Program<'info, Token>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+
+    /// Fee exemption entry of the program depositing through CPI, if any.
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    /// The instructions sysvar, used to identify the program depositing through CPI.
+    /// CHECK: Address is validated by the constraint.
+    #[account(address = sysvar::instructions::id())]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
 }
 
 /// Instruction context for depositing SPL tokens.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct DepositSplToken<'info> {
     /// The account of the signer making the deposit.
@@ -88,24 +240,116 @@ pub struct DepositSplToken<'info> {
     pub whitelist_entry: Account<'info, WhitelistEntry>,
 
     /// The mint account of the SPL token being deposited.
-    pub mint_account: Account<'info, Mint>,
+    pub mint_account: InterfaceAccount<'info, Mint>,
 
-    /// The token program.
-    pub token_program: Program<'info, Token>,
+    /// The token program, SPL Token or Token-2022.
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The source token account owned by the signer.
     #[account(mut, constraint = from.mint == mint_account.key())]
-    pub from: Account<'info, TokenAccount>,
+    pub from: InterfaceAccount<'info, TokenAccount>,
 
     /// The destination token account owned by the PDA.
     #[account(mut)]
-    pub to: Account<'info, TokenAccount>,
+    pub to: InterfaceAccount<'info, TokenAccount>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+
+    /// Fee exemption entry of the program depositing through CPI, if any.
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    /// The instructions sysvar, used to identify the program depositing through CPI.
+    /// CHECK: Address is validated by the constraint.
+    #[account(address = sysvar::instructions::id())]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+/// Instruction context for swapping a token through the swap program and depositing the output.
+/// The accounts of the swap instruction are passed as remaining accounts.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositWithSwap<'info> {
+    /// The account of the signer making the deposit.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The whitelist entry account for the output SPL token.
+    #[account(seeds = [b"whitelist", mint_account.key().as_ref()], bump)]
+    pub whitelist_entry: Account<'info, WhitelistEntry>,
+
+    /// The mint account of the output SPL token being deposited.
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// The token program of the output mint, SPL Token or Token-2022.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The source token account owned by the signer, holding the token being swapped.
+    #[account(mut, token::authority = signer)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    /// The destination token account owned by the PDA, receiving the swap output.
+    #[account(mut)]
+    pub to: InterfaceAccount<'info, TokenAccount>,
+
+    /// The swap program configured in the PDA.
+    /// CHECK: Address is validated against the swap program stored in PDA.
+    #[account(address = pda.swap_program)]
+    pub swap_program: UncheckedAccount<'info>,
 
     /// The system program.
     pub system_program: Program<'info, System>,
+
+    /// Fee exemption entry of the program depositing through CPI, if any.
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    /// The instructions sysvar, used to identify the program depositing through CPI.
+    /// CHECK: Address is validated by the constraint.
+    #[account(address = sysvar::instructions::id())]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+/// Instruction context for swapping a token into SOL before depositing it.
+/// The accounts of the swap instruction are passed as remaining accounts,
+/// and the swap output is credited to the PDA.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositSolWithSwap<'info> {
+    /// The account of the signer making the deposit.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA, receiving the swap output.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The source token account owned by the signer, holding the token being swapped.
+    #[account(mut, token::authority = signer)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    /// The swap program configured in the PDA.
+    /// CHECK: Address is validated against the swap program stored in PDA.
+    #[account(address = pda.swap_program)]
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+
+    /// Fee exemption entry of the program depositing through CPI, if any.
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    /// The instructions sysvar, used to identify the program depositing through CPI.
+    /// CHECK: Address is validated by the constraint.
+    #[account(address = sysvar::instructions::id())]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
 }
 
 /// Instruction context for call operation.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Call<'info> {
     /// The account of the signer making the call.
@@ -114,7 +358,15 @@ pub struct Call<'info> {
 }
 
 /// Instruction context for SOL withdrawal operations.
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(
+    amount: u64,
+    signature: [u8; 64],
+    recovery_id: u8,
+    message_hash: [u8; 32],
+    nonce: u64
+)]
 pub struct Withdraw<'info> {
     /// The account of the signer making the withdrawal.
     #[account(mut)]
@@ -124,14 +376,88 @@ pub struct Withdraw<'info> {
     #[account(mut, seeds = [b"meta"], bump)]
     pub pda: Account<'info, Pda>,
 
+    /// Rate limit PDA for SOL, uninitialized if SOL outbounds are not rate limited.
+    /// CHECK: Deserialized and updated only if initialized.
+    #[account(mut, seeds = [b"rate_limit", Pubkey::default().as_ref()], bump)]
+    pub rate_limit: UncheckedAccount<'info>,
+
     /// The recipient account for the withdrawn SOL.
     /// CHECK: Recipient account is not read; ownership validation is unnecessary.
     #[account(mut)]
     pub recipient: UncheckedAccount<'info>,
+
+    /// Pending withdrawal PDA, created if the amount is above the delay threshold.
+    /// CHECK: Created during instruction processing only if the withdrawal is queued.
+    #[account(mut, seeds = [b"pending_withdrawal", message_hash.as_ref()], bump)]
+    pub pending_withdrawal: UncheckedAccount<'info>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction context for SOL withdrawals delivered as wrapped SOL.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+    amount: u64,
+    signature: [u8; 64],
+    recovery_id: u8,
+    message_hash: [u8; 32],
+    nonce: u64
+)]
+pub struct WithdrawWsol<'info> {
+    /// The account of the signer making the withdrawal.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// Rate limit PDA for SOL, uninitialized if SOL outbounds are not rate limited.
+    /// CHECK: Deserialized and updated only if initialized.
+    #[account(mut, seeds = [b"rate_limit", Pubkey::default().as_ref()], bump)]
+    pub rate_limit: UncheckedAccount<'info>,
+
+    /// The recipient of the wrapped SOL.
+    /// CHECK: Recipient account is not read; ownership validation is unnecessary.
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The recipient's wrapped SOL associated token account, created if needed.
+    /// CHECK: Validation will occur during instruction processing.
+    #[account(mut)]
+    pub recipient_ata: AccountInfo<'info>,
+
+    /// The native mint.
+    #[account(address = native_mint::ID)]
+    pub native_mint: InterfaceAccount<'info, Mint>,
+
+    /// Pending withdrawal PDA, created if the amount is above the delay threshold.
+    /// CHECK: Created during instruction processing only if the withdrawal is queued.
+    #[account(mut, seeds = [b"pending_withdrawal", message_hash.as_ref()], bump)]
+    pub pending_withdrawal: UncheckedAccount<'info>,
+
+    /// The SPL Token program.
+    pub token_program: Program<'info, Token>,
+
+    /// The associated token program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
 }
 
 /// Instruction context for SPL token withdrawal operations.
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(
+    decimals: u8,
+    amount: u64,
+    signature: [u8; 64],
+    recovery_id: u8,
+    message_hash: [u8; 32],
+    nonce: u64
+)]
 pub struct WithdrawSPLToken<'info> {
     /// The account of the signer making the withdrawal.
     #[account(mut)]
@@ -142,11 +468,26 @@ pub struct WithdrawSPLToken<'info> {
     pub pda: Account<'info, Pda>,
 
     /// The associated token account for the Gateway PDA.
-    #[account(mut, associated_token::mint = mint_account, associated_token::authority = pda)]
-    pub pda_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = pda,
+        associated_token::token_program = token_program
+    )]
+    pub pda_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// The mint account of the SPL token being withdrawn.
-    pub mint_account: Account<'info, Mint>,
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// The whitelist entry of the SPL token being withdrawn, which may be withdraw-only.
+    /// CHECK: Deserialized only if it exists in the current layout, legacy entries carry no settings.
+    #[account(seeds = [b"whitelist", mint_account.key().as_ref()], bump)]
+    pub whitelist_entry: UncheckedAccount<'info>,
+
+    /// Rate limit PDA for the mint, uninitialized if the mint is not rate limited.
+    /// CHECK: Deserialized and updated only if initialized.
+    #[account(mut, seeds = [b"rate_limit", mint_account.key().as_ref()], bump)]
+    pub rate_limit: UncheckedAccount<'info>,
 
     /// The recipient account for the withdrawn tokens.
     /// CHECK: Recipient account is not read; ownership validation is unnecessary.
@@ -157,8 +498,13 @@ pub struct WithdrawSPLToken<'info> {
     #[account(mut)]
     pub recipient_ata: AccountInfo<'info>,
 
-    /// The token program.
-    pub token_program: Program<'info, Token>,
+    /// Pending withdrawal PDA, created if the amount is above the delay threshold.
+    /// CHECK: Created during instruction processing only if the withdrawal is queued.
+    #[account(mut, seeds = [b"pending_withdrawal", message_hash.as_ref()], bump)]
+    pub pending_withdrawal: UncheckedAccount<'info>,
+
+    /// The token program, SPL Token or Token-2022.
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program.
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -167,6 +513,7 @@ pub struct WithdrawSPLToken<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ExecuteSPLToken<'info> {
     /// The account of the signer making the withdrawal.
@@ -178,11 +525,21 @@ pub struct ExecuteSPLToken<'info> {
     pub pda: Account<'info, Pda>,
 
     /// The associated token account for the Gateway PDA.
-    #[account(mut, associated_token::mint = mint_account, associated_token::authority = pda)]
-    pub pda_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = pda,
+        associated_token::token_program = token_program
+    )]
+    pub pda_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// The mint account of the SPL token being withdrawn.
-    pub mint_account: Account<'info, Mint>,
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// Rate limit PDA for the mint, uninitialized if the mint is not rate limited.
+    /// CHECK: Deserialized and updated only if initialized.
+    #[account(mut, seeds = [b"rate_limit", mint_account.key().as_ref()], bump)]
+    pub rate_limit: UncheckedAccount<'info>,
 
     /// The destination program.
     /// CHECK: This is arbitrary program.
@@ -203,17 +560,33 @@ pub struct ExecuteSPLToken<'info> {
     #[account(mut)]
     pub destination_program_pda_ata: AccountInfo<'info>,
 
-    /// The token program.
-    pub token_program: Program<'info, Token>,
+    /// The token program, SPL Token or Token-2022.
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The system program.
     pub system_program: Program<'info, System>,
+
+    /// Account list published by the destination program, checked against the forwarded accounts if it exists.
+    /// CHECK: Deserialized only if owned by the destination program.
+    #[account(
+        seeds = [b"expected_accounts"],
+        bump,
+        seeds::program = destination_program.key()
+    )]
+    pub expected_accounts: UncheckedAccount<'info>,
+
+    /// Gateway caller PDA of the destination program, signing the call when forwarded so the
+    /// destination program can authenticate the gateway.
+    /// CHECK: Holds no data, only used as a signer.
+    #[account(seeds = [b"caller", destination_program.key().as_ref()], bump)]
+    pub caller: UncheckedAccount<'info>,
 }
 
 /// Instruction context for updating the TSS address.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateTss<'info> {
     /// The account of the signer performing the update.
@@ -223,9 +596,15 @@ pub struct UpdateTss<'info> {
     /// Gateway PDA.
     #[account(mut, seeds = [b"meta"], bump)]
     pub pda: Account<'info, Pda>,
+
+    /// Admin config PDA, must be uninitialized for direct updates.
+    /// CHECK: Only checked to be empty, multisig updates go through admin proposals.
+    #[account(seeds = [b"admin_config"], bump)]
+    pub admin_config: UncheckedAccount<'info>,
 }
 
 /// Instruction context for updating the PDA authority.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateAuthority<'info> {
     /// The account of the signer performing the update.
@@ -235,9 +614,15 @@ pub struct UpdateAuthority<'info> {
     /// Gateway PDA.
     #[account(mut, seeds = [b"meta"], bump)]
     pub pda: Account<'info, Pda>,
+
+    /// Admin config PDA, must be uninitialized for direct updates.
+    /// CHECK: Only checked to be empty, multisig updates go through admin proposals.
+    #[account(seeds = [b"admin_config"], bump)]
+    pub admin_config: UncheckedAccount<'info>,
 }
 
 /// Instruction context for resetting the PDA nonce.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ResetNonce<'info> {
     /// The account of the signer performing the update.
@@ -247,9 +632,15 @@ pub struct ResetNonce<'info> {
     /// Gateway PDA.
     #[account(mut, seeds = [b"meta"], bump)]
     pub pda: Account<'info, Pda>,
+
+    /// Admin config PDA, must be uninitialized for direct updates.
+    /// CHECK: Only checked to be empty, multisig updates go through admin proposals.
+    #[account(seeds = [b"admin_config"], bump)]
+    pub admin_config: UncheckedAccount<'info>,
 }
 
 /// Instruction context for pausing or unpausing deposits.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdatePaused<'info> {
     /// The account of the signer performing the update.
@@ -261,7 +652,127 @@ pub struct UpdatePaused<'info> {
     pub pda: Account<'info, Pda>,
 }
 
+/// Instruction context for updating the deposit fees.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateDepositFee<'info> {
+    /// The account of the signer performing the update.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+}
+
+/// Instruction context for updating the minimum SOL deposit.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMinDeposit<'info> {
+    /// The account of the signer performing the update.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+}
+
+/// Instruction context for updating the swap program.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateSwapProgram<'info> {
+    /// The account of the signer performing the update.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+}
+
+/// Instruction context for updating the treasury address.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTreasury<'info> {
+    /// The account of the signer performing the update.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+}
+
+/// Instruction context for withdrawing collected deposit fees.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    /// The account of the authority performing the withdrawal.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The treasury account receiving the fees.
+    /// CHECK: Address is validated against the treasury stored in PDA.
+    #[account(mut, address = pda.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+/// Instruction context for exempting a program from deposit fees.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct AddFeeExemption<'info> {
+    /// The account of the authority performing the operation.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The fee exemption account being initialized.
+    #[account(
+        init,
+        space = 8 + FeeExemption::INIT_SPACE,
+        payer = signer,
+        seeds = [b"fee_exemption", program_id.as_ref()],
+        bump
+    )]
+    pub fee_exemption: Account<'info, FeeExemption>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction context for removing a program's deposit fee exemption.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveFeeExemption<'info> {
+    /// The account of the authority performing the operation.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The fee exemption account being closed.
+    #[account(
+        mut,
+        seeds = [b"fee_exemption", fee_exemption.program_id.as_ref()],
+        bump,
+        close = signer,
+    )]
+    pub fee_exemption: Account<'info, FeeExemption>,
+}
+
 /// Instruction context for whitelisting SPL tokens.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Whitelist<'info> {
     /// The account of the authority performing the operation.
@@ -272,10 +783,10 @@ pub struct Whitelist<'info> {
     #[account(mut, seeds = [b"meta"], bump)]
     pub pda: Account<'info, Pda>,
 
-    /// The whitelist entry account being initialized.
+    /// The whitelist entry account, initialized or re-enabled if the mint is withdraw-only.
     #[account(
-        init,
-        space = 8,
+        init_if_needed,
+        space = 8 + WhitelistEntry::INIT_SPACE,
         payer = authority,
         seeds = [b"whitelist", whitelist_candidate.key().as_ref()],
         bump
@@ -283,13 +794,43 @@ pub struct Whitelist<'info> {
     pub whitelist_entry: Account<'info, WhitelistEntry>,
 
     /// The mint account of the SPL token being whitelisted.
-    pub whitelist_candidate: Account<'info, Mint>,
+    pub whitelist_candidate: InterfaceAccount<'info, Mint>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction context for migrating a whitelist entry to the current layout.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpgradeWhitelistEntry<'info> {
+    /// The account of the authority performing the migration, paying any rent top-up.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The whitelist entry being migrated.
+    /// CHECK: Legacy layout is validated during migration.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"whitelist", mint_account.key().as_ref()],
+        bump
+    )]
+    pub whitelist_entry: UncheckedAccount<'info>,
+
+    /// The mint account of the whitelisted SPL token.
+    pub mint_account: InterfaceAccount<'info, Mint>,
 
     /// The system program.
     pub system_program: Program<'info, System>,
 }
 
 /// Instruction context for unwhitelisting SPL tokens.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Unwhitelist<'info> {
     /// The account of the authority performing the operation.
@@ -300,17 +841,82 @@ pub struct Unwhitelist<'info> {
     #[account(mut, seeds = [b"meta"], bump)]
     pub pda: Account<'info, Pda>,
 
-    /// The whitelist entry account being closed.
+    /// The whitelist entry account being marked withdraw-only.
     #[account(
         mut,
         seeds = [b"whitelist", whitelist_candidate.key().as_ref()],
         bump,
-        close = authority,
     )]
     pub whitelist_entry: Account<'info, WhitelistEntry>,
 
     /// The mint account of the SPL token being unwhitelisted.
-    pub whitelist_candidate: Account<'info, Mint>,
+    pub whitelist_candidate: InterfaceAccount<'info, Mint>,
+}
+
+/// Instruction context for updating the per-mint settings of a whitelist entry.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateWhitelistEntry<'info> {
+    /// The account of the authority performing the update.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The whitelist entry being updated.
+    #[account(mut, seeds = [b"whitelist", mint_account.key().as_ref()], bump)]
+    pub whitelist_entry: Account<'info, WhitelistEntry>,
+
+    /// The mint account of the whitelisted SPL token.
+    pub mint_account: InterfaceAccount<'info, Mint>,
+}
+
+/// Instruction context for sweeping and closing the gateway ATA of an unwhitelisted mint.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseMintVault<'info> {
+    /// The account of the authority performing the operation, receiving the rent.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The whitelist entry of the mint, which must be withdraw-only and is closed.
+    #[account(
+        mut,
+        seeds = [b"whitelist", mint_account.key().as_ref()],
+        bump,
+        close = signer,
+    )]
+    pub whitelist_entry: Account<'info, WhitelistEntry>,
+
+    /// The mint account of the unwhitelisted SPL token.
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// The associated token account for the Gateway PDA being closed.
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = pda,
+        associated_token::token_program = token_program
+    )]
+    pub pda_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// The treasury token account receiving the remaining balance.
+    #[account(
+        mut,
+        token::mint = mint_account,
+        token::authority = pda.treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// The token program, SPL Token or Token-2022.
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 /// Instruction context for checking upgrade status
@@ -319,3 +925,270 @@ pub struct Upgrade<'info> {
     /// The account of the signer checking the upgrade
     pub signer: Signer<'info>,
 }
+
+/// Instruction context for initializing the admin config.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeAdminConfig<'info> {
+    /// The account of the authority initializing the admin config.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The admin config account being initialized.
+    #[account(
+        init,
+        space = 8 + AdminConfig::INIT_SPACE,
+        payer = signer,
+        seeds = [b"admin_config"],
+        bump
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction context for proposing an admin action.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAdminAction<'info> {
+    /// The admin signer proposing the action.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Admin config PDA.
+    #[account(mut, seeds = [b"admin_config"], bump)]
+    pub admin_config: Account<'info, AdminConfig>,
+
+    /// The proposal account being initialized.
+    #[account(
+        init,
+        space = 8 + AdminProposal::INIT_SPACE,
+        payer = signer,
+        seeds = [b"admin_proposal", admin_config.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction context for approving an admin action.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ApproveAdminAction<'info> {
+    /// The admin signer approving the action.
+    pub signer: Signer<'info>,
+
+    /// Admin config PDA.
+    #[account(seeds = [b"admin_config"], bump)]
+    pub admin_config: Account<'info, AdminConfig>,
+
+    /// The proposal being approved.
+    #[account(mut, seeds = [b"admin_proposal", proposal_id.to_le_bytes().as_ref()], bump)]
+    pub proposal: Account<'info, AdminProposal>,
+}
+
+/// Instruction context for executing an approved admin action.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteAdminAction<'info> {
+    /// The admin signer executing the action.
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// Admin config PDA.
+    #[account(mut, seeds = [b"admin_config"], bump)]
+    pub admin_config: Account<'info, AdminConfig>,
+
+    /// The proposal being executed, closed to its proposer.
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"admin_proposal", proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    /// The proposer receiving the proposal rent.
+    /// CHECK: Address is validated against the proposer stored in the proposal.
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+/// Instruction context for cancelling an admin action.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CancelAdminAction<'info> {
+    /// The proposer cancelling the action.
+    pub signer: Signer<'info>,
+
+    /// The proposal being cancelled, closed to its proposer.
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"admin_proposal", proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    /// The proposer receiving the proposal rent.
+    /// CHECK: Address is validated against the proposer stored in the proposal.
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+/// Instruction context for setting an outbound rate limit.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct SetRateLimit<'info> {
+    /// The account of the authority performing the operation.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The rate limit account being initialized.
+    #[account(
+        init,
+        space = 8 + RateLimit::INIT_SPACE,
+        payer = signer,
+        seeds = [b"rate_limit", mint.as_ref()],
+        bump
+    )]
+    pub rate_limit: Account<'info, RateLimit>,
+
+    /// The system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction context for updating an outbound rate limit.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct UpdateRateLimit<'info> {
+    /// The account of the authority performing the operation.
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The rate limit account being updated.
+    #[account(mut, seeds = [b"rate_limit", mint.as_ref()], bump)]
+    pub rate_limit: Account<'info, RateLimit>,
+}
+
+/// Instruction context for removing an outbound rate limit.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct RemoveRateLimit<'info> {
+    /// The account of the authority performing the operation.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The rate limit account being closed.
+    #[account(mut, close = signer, seeds = [b"rate_limit", mint.as_ref()], bump)]
+    pub rate_limit: Account<'info, RateLimit>,
+}
+
+/// Instruction context for finalizing a queued withdrawal.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(message_hash: [u8; 32])]
+pub struct FinalizeWithdrawal<'info> {
+    /// The account of the signer finalizing the withdrawal.
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(mut, seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The pending withdrawal being finalized.
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"pending_withdrawal", message_hash.as_ref()],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    /// The account that paid the pending withdrawal rent.
+    /// CHECK: Address is validated against the payer stored in the pending withdrawal.
+    #[account(mut, address = pending_withdrawal.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// The recipient of the withdrawal.
+    /// CHECK: Address is validated against the recipient stored in the pending withdrawal.
+    #[account(mut, address = pending_withdrawal.recipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The associated token account for the Gateway PDA, required for SPL withdrawals.
+    #[account(mut)]
+    pub pda_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint account of the withdrawn SPL token, required for SPL withdrawals.
+    pub mint_account: Option<InterfaceAccount<'info, Mint>>,
+
+    /// The recipient's associated token account, required for SPL withdrawals.
+    /// CHECK: Address is validated against the pending withdrawal during instruction processing.
+    #[account(mut)]
+    pub recipient_ata: Option<UncheckedAccount<'info>>,
+
+    /// The token program, required for SPL withdrawals.
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Instruction context for cancelling a queued withdrawal.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(message_hash: [u8; 32])]
+pub struct CancelWithdrawal<'info> {
+    /// The account of the authority cancelling the withdrawal.
+    pub signer: Signer<'info>,
+
+    /// Gateway PDA.
+    #[account(seeds = [b"meta"], bump)]
+    pub pda: Account<'info, Pda>,
+
+    /// The pending withdrawal being cancelled.
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"pending_withdrawal", message_hash.as_ref()],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    /// The account that paid the pending withdrawal rent.
+    /// CHECK: Address is validated against the payer stored in the pending withdrawal.
+    #[account(mut, address = pending_withdrawal.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Rate limit PDA of the withdrawn asset, credited back with the cancelled amount.
+    #[account(
+        mut,
+        seeds = [b"rate_limit", pending_withdrawal.rate_limit_mint.as_ref()],
+        bump
+    )]
+    pub rate_limit: Account<'info, RateLimit>,
+}
@@ -23,4 +23,88 @@ pub enum Errors {
     EmptyReceiver,
     #[msg("InvalidInstructionData")]
     InvalidInstructionData,
+    #[msg("InvalidFeeExemption")]
+    InvalidFeeExemption,
+    #[msg("InsufficientCollectedFees")]
+    InsufficientCollectedFees,
+    #[msg("InvalidAdminConfig")]
+    InvalidAdminConfig,
+    #[msg("SignerIsNotAdmin")]
+    SignerIsNotAdmin,
+    #[msg("ProposalAlreadyApproved")]
+    ProposalAlreadyApproved,
+    #[msg("InsufficientApprovals")]
+    InsufficientApprovals,
+    #[msg("MultisigRequired")]
+    MultisigRequired,
+    #[msg("InvalidTssRotation")]
+    InvalidTssRotation,
+    #[msg("InvalidPdaLayout")]
+    InvalidPdaLayout,
+    #[msg("PdaAlreadyUpgraded")]
+    PdaAlreadyUpgraded,
+    #[msg("InvalidRateLimit")]
+    InvalidRateLimit,
+    #[msg("WithdrawalAboveLimit")]
+    WithdrawalAboveLimit,
+    #[msg("RateLimitExceeded")]
+    RateLimitExceeded,
+    #[msg("AmountAboveDelayThreshold")]
+    AmountAboveDelayThreshold,
+    #[msg("WithdrawalNotReleasable")]
+    WithdrawalNotReleasable,
+    #[msg("InvalidPendingWithdrawal")]
+    InvalidPendingWithdrawal,
+    #[msg("UnsupportedMintExtension")]
+    UnsupportedMintExtension,
+    #[msg("DepositBalanceMismatch")]
+    DepositBalanceMismatch,
+    #[msg("MintWithdrawOnly")]
+    MintWithdrawOnly,
+    #[msg("MintNotWithdrawOnly")]
+    MintNotWithdrawOnly,
+    #[msg("InvalidWhitelistConfig")]
+    InvalidWhitelistConfig,
+    #[msg("DepositBelowMinimum")]
+    DepositBelowMinimum,
+    #[msg("DepositAboveMaximum")]
+    DepositAboveMaximum,
+    #[msg("DecimalsMismatch")]
+    DecimalsMismatch,
+    #[msg("InvalidTokenProgram")]
+    InvalidTokenProgram,
+    #[msg("ArithmeticOverflow")]
+    ArithmeticOverflow,
+    #[msg("InvalidReceiver")]
+    InvalidReceiver,
+    #[msg("InvalidSwapProgram")]
+    InvalidSwapProgram,
+    #[msg("SwapInputExceeded")]
+    SwapInputExceeded,
+    #[msg("SlippageExceeded")]
+    SlippageExceeded,
+    #[msg("NotNativeMint")]
+    NotNativeMint,
+    #[msg("InvalidDepositAssets")]
+    InvalidDepositAssets,
+    #[msg("InvalidExpectedAccounts")]
+    InvalidExpectedAccounts,
+    #[msg("UnexpectedForwardedAccounts")]
+    UnexpectedForwardedAccounts,
+    #[msg("InvalidRefundAccounts")]
+    InvalidRefundAccounts,
+    #[msg("DepositFeeTooHigh")]
+    DepositFeeTooHigh,
+    #[msg("InsufficientCustodyBalance")]
+    InsufficientCustodyBalance,
+    #[msg("ProposalExpired")]
+    ProposalExpired,
+    #[msg("InvalidWhitelistEntryLayout")]
+    InvalidWhitelistEntryLayout,
+    #[msg("WhitelistEntryAlreadyUpgraded")]
+    WhitelistEntryAlreadyUpgraded,
+    #[msg("InvalidSwapAccounts")]
+    InvalidSwapAccounts,
+    #[msg("SignerIsNotProposer")]
+    SignerIsNotProposer,
 }
@@ -0,0 +1,407 @@
+use crate::state::{
+    AdminAction, DepositAsset, FailureCode, InstructionId, Receiver, RevertOptions,
+};
+use anchor_lang::prelude::*;
+
+/// Version of the event schema, carried in every event emitted by the gateway.
+/// Version 1 is the first released schema and covers the event layouts defined in this file.
+/// Any later change to the layout of an event, such as adding a field or changing a field type,
+/// must increment this version so off-chain decoders can tell the layouts apart.
+pub const EVENT_SCHEMA_VERSION: u8 = 1;
+
+/// Emitted when the gateway PDA is initialized.
+#[event]
+pub struct GatewayInitialized {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub tss_address: [u8; 20],
+    pub chain_id: u64,
+}
+
+/// Emitted when SOL or SPL tokens are deposited to ZetaChain.
+/// `mint` is `None` for SOL deposits.
+#[event]
+pub struct Deposited {
+    pub version: u8,
+    pub sender: Pubkey,
+    pub receiver: [u8; 20],
+    pub amount: u64,
+    pub fee: u64,
+    pub mint: Option<Pubkey>,
+    pub revert_options: Option<RevertOptions>,
+}
+
+/// Emitted when SOL or SPL tokens are deposited and a contract is called on ZetaChain.
+/// `mint` is `None` for SOL deposits.
+#[event]
+pub struct DepositedAndCalled {
+    pub version: u8,
+    pub sender: Pubkey,
+    pub receiver: [u8; 20],
+    pub amount: u64,
+    pub fee: u64,
+    pub mint: Option<Pubkey>,
+    pub message: Vec<u8>,
+    pub revert_options: Option<RevertOptions>,
+}
+
+/// Emitted when tokens are swapped before being deposited by `deposit_with_swap` or `deposit_sol_with_swap`.
+/// It precedes the `Deposited` event crediting the output amount. The output mint is `None` for SOL.
+#[event]
+pub struct SwappedForDeposit {
+    pub version: u8,
+    pub sender: Pubkey,
+    pub swap_program: Pubkey,
+    pub input_mint: Pubkey,
+    pub amount_in: u64,
+    pub output_mint: Option<Pubkey>,
+    pub amount_out: u64,
+}
+
+/// Emitted when a contract is called on ZetaChain without a deposit.
+#[event]
+pub struct Called {
+    pub version: u8,
+    pub sender: Pubkey,
+    pub receiver: [u8; 20],
+    pub message: Vec<u8>,
+    pub revert_options: Option<RevertOptions>,
+}
+
+/// Emitted when SOL and several SPL tokens are deposited as a single inbound, charged a single fee.
+/// `assets` holds the amounts received by the gateway, and `message` is `None` if no contract is called.
+#[event]
+pub struct DepositedMulti {
+    pub version: u8,
+    pub sender: Pubkey,
+    pub receiver: [u8; 20],
+    pub sol_amount: u64,
+    pub assets: Vec<DepositAsset>,
+    pub fee: u64,
+    pub message: Option<Vec<u8>>,
+    pub revert_options: Option<RevertOptions>,
+}
+
+/// Emitted when SOL or SPL tokens are deposited through a versioned deposit.
+/// `mint` is `None` for SOL deposits.
+#[event]
+pub struct DepositedV2 {
+    pub version: u8,
+    pub sender: Pubkey,
+    pub receiver: Receiver,
+    pub amount: u64,
+    pub fee: u64,
+    pub mint: Option<Pubkey>,
+    pub revert_options: Option<RevertOptions>,
+}
+
+/// Emitted when SOL or SPL tokens are deposited and a contract is called through a versioned deposit.
+/// `mint` is `None` for SOL deposits.
+#[event]
+pub struct DepositedAndCalledV2 {
+    pub version: u8,
+    pub sender: Pubkey,
+    pub receiver: Receiver,
+    pub amount: u64,
+    pub fee: u64,
+    pub mint: Option<Pubkey>,
+    pub message: Vec<u8>,
+    pub revert_options: Option<RevertOptions>,
+}
+
+/// Emitted when a contract is called through a versioned call without a deposit.
+#[event]
+pub struct CalledV2 {
+    pub version: u8,
+    pub sender: Pubkey,
+    pub receiver: Receiver,
+    pub message: Vec<u8>,
+    pub revert_options: Option<RevertOptions>,
+}
+
+/// Emitted when SOL or SPL tokens are withdrawn by TSS.
+/// `mint` is `None` for SOL withdrawals.
+#[event]
+pub struct Withdrawn {
+    pub version: u8,
+    pub nonce: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub mint: Option<Pubkey>,
+}
+
+/// Emitted when a withdrawal above the delay threshold is queued.
+/// `mint` is `None` for SOL withdrawals.
+#[event]
+pub struct WithdrawalQueued {
+    pub version: u8,
+    pub nonce: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub mint: Option<Pubkey>,
+    pub release_slot: u64,
+}
+
+/// Emitted when the authority cancels a queued withdrawal.
+/// `mint` is `None` for SOL withdrawals.
+#[event]
+pub struct WithdrawalCancelled {
+    pub version: u8,
+    pub nonce: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub mint: Option<Pubkey>,
+}
+
+/// Emitted when `on_call` or `on_revert` is executed on a destination program.
+/// `mint` is `None` for SOL executions.
+#[event]
+pub struct Executed {
+    pub version: u8,
+    pub nonce: u64,
+    pub destination_program: Pubkey,
+    pub sender: Vec<u8>,
+    pub amount: u64,
+    pub mint: Option<Pubkey>,
+    pub data: Vec<u8>,
+    pub revert: bool,
+}
+
+/// Emitted after `Executed` once the destination program call completed.
+/// `return_data` is the data returned by the destination program, empty if it returned none,
+/// and `truncated` is set if it was cut to `MAX_EXECUTE_RETURN_DATA_SIZE` bytes.
+#[event]
+pub struct ExecuteCompleted {
+    pub version: u8,
+    pub nonce: u64,
+    pub destination_program: Pubkey,
+    pub revert: bool,
+    pub return_data: Vec<u8>,
+    pub truncated: bool,
+}
+
+/// Emitted when TSS increments the nonce for a failed outbound.
+#[event]
+pub struct NonceIncremented {
+    pub version: u8,
+    pub nonce: u64,
+    pub amount: u64,
+    pub failure_reason: String,
+}
+
+/// Emitted when TSS increments the nonce for a failed outbound with a typed failure code.
+/// `refund_address` is the revert or abort address the amount was refunded to, `None` if it was not refunded,
+/// and `mint` is `None` for SOL.
+#[event]
+pub struct OutboundFailed {
+    pub version: u8,
+    pub nonce: u64,
+    pub amount: u64,
+    pub failure_code: FailureCode,
+    pub original_instruction_id: InstructionId,
+    pub refund_address: Option<Pubkey>,
+    pub mint: Option<Pubkey>,
+}
+
+/// Emitted when an SPL mint is whitelisted.
+#[event]
+pub struct SplMintWhitelisted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub whitelist_entry: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Emitted when an SPL mint is unwhitelisted and becomes withdraw-only.
+#[event]
+pub struct SplMintUnwhitelisted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub whitelist_entry: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Emitted when the per-mint settings of a whitelist entry are updated.
+#[event]
+pub struct WhitelistEntryUpdated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub min_deposit: u64,
+    pub max_deposit: u64,
+    pub deposit_paused: bool,
+    pub deposit_fee: Option<u64>,
+}
+
+/// Emitted when the gateway ATA of an unwhitelisted mint is swept to the treasury and closed.
+#[event]
+pub struct MintVaultClosed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub treasury_ata: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a TSS rotation is scheduled.
+/// Both TSS addresses are accepted from `activation_slot` until `overlap_end_slot`.
+#[event]
+pub struct TssUpdateScheduled {
+    pub version: u8,
+    pub tss_address: [u8; 20],
+    pub pending_tss_address: [u8; 20],
+    pub activation_slot: u64,
+    pub overlap_end_slot: u64,
+    pub nonce: u64,
+}
+
+/// Emitted when the gateway PDA is migrated to a new layout version.
+#[event]
+pub struct PdaUpgraded {
+    pub version: u8,
+    pub pda_version: u8,
+}
+
+/// Emitted when a whitelist entry is migrated to a new layout version.
+#[event]
+pub struct WhitelistEntryUpgraded {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub entry_version: u8,
+}
+
+/// Emitted when the PDA authority is updated.
+#[event]
+pub struct AuthorityUpdated {
+    pub version: u8,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Emitted when the PDA nonce is reset by the authority.
+#[event]
+pub struct NonceReset {
+    pub version: u8,
+    pub previous_nonce: u64,
+    pub nonce: u64,
+}
+
+/// Emitted when the deposit fees are updated.
+#[event]
+pub struct DepositFeeUpdated {
+    pub version: u8,
+    pub deposit_fee: u64,
+    pub spl_deposit_fee: u64,
+}
+
+/// Emitted when the minimum SOL deposit is updated.
+#[event]
+pub struct MinDepositUpdated {
+    pub version: u8,
+    pub min_deposit: u64,
+}
+
+/// Emitted when the swap program used by `deposit_with_swap` is updated.
+#[event]
+pub struct SwapProgramUpdated {
+    pub version: u8,
+    pub swap_program: Pubkey,
+}
+
+/// Emitted when a program is exempted from deposit fees.
+#[event]
+pub struct FeeExemptionAdded {
+    pub version: u8,
+    pub program_id: Pubkey,
+}
+
+/// Emitted when a program's deposit fee exemption is removed.
+#[event]
+pub struct FeeExemptionRemoved {
+    pub version: u8,
+    pub program_id: Pubkey,
+}
+
+/// Emitted when the treasury address is updated.
+#[event]
+pub struct TreasuryUpdated {
+    pub version: u8,
+    pub previous_treasury: Pubkey,
+    pub treasury: Pubkey,
+}
+
+/// Emitted when collected deposit fees are withdrawn to the treasury.
+#[event]
+pub struct FeesWithdrawn {
+    pub version: u8,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub collected_fees: u64,
+}
+
+/// Emitted when the admin signers or threshold are set.
+#[event]
+pub struct AdminConfigUpdated {
+    pub version: u8,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+/// Emitted when an admin signer proposes an admin action.
+#[event]
+pub struct AdminActionProposed {
+    pub version: u8,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+}
+
+/// Emitted when an admin signer approves an admin action.
+#[event]
+pub struct AdminActionApproved {
+    pub version: u8,
+    pub proposal_id: u64,
+    pub approver: Pubkey,
+    pub approvals: u8,
+}
+
+/// Emitted when an admin action is cancelled before execution.
+#[event]
+pub struct AdminActionCancelled {
+    pub version: u8,
+    pub proposal_id: u64,
+    pub cancelled_by: Pubkey,
+}
+
+/// Emitted when an approved admin action is executed.
+#[event]
+pub struct AdminActionExecuted {
+    pub version: u8,
+    pub proposal_id: u64,
+    pub action: AdminAction,
+}
+
+/// Emitted when an outbound rate limit is set or updated.
+/// `mint` is `Pubkey::default()` for SOL.
+#[event]
+pub struct RateLimitSet {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub max_per_withdrawal: u64,
+    pub max_per_window: u64,
+    pub window_slots: u64,
+    pub delay_threshold: u64,
+    pub delay_slots: u64,
+}
+
+/// Emitted when an outbound rate limit is removed.
+#[event]
+pub struct RateLimitRemoved {
+    pub version: u8,
+    pub mint: Pubkey,
+}
+
+/// Emitted when deposits are paused or unpaused.
+#[event]
+pub struct DepositPausedUpdated {
+    pub version: u8,
+    pub deposit_paused: bool,
+}
@@ -1,15 +1,31 @@
 use crate::{
     contexts::{
-        Initialize, ResetNonce, Unwhitelist, UpdateAuthority, UpdatePaused, UpdateTss, Whitelist,
+        AddFeeExemption, CancelWithdrawal, CloseMintVault, Initialize, RemoveFeeExemption,
+        RemoveRateLimit, ResetNonce, SetRateLimit, Unwhitelist, UpdateAuthority, UpdateDepositFee,
+        UpdateMinDeposit, UpdatePaused, UpdateRateLimit, UpdateSwapProgram, UpdateTreasury,
+        UpdateTss, UpdateWhitelistEntry, UpgradePda, UpgradeWhitelistEntry, Whitelist,
+        WithdrawFees,
     },
-    state::InstructionId,
+    errors::Errors,
+    events::{
+        AuthorityUpdated, DepositFeeUpdated, DepositPausedUpdated, FeeExemptionAdded,
+        FeeExemptionRemoved, FeesWithdrawn, GatewayInitialized, MinDepositUpdated, MintVaultClosed,
+        NonceReset, PdaUpgraded, RateLimitRemoved, RateLimitSet, SplMintUnwhitelisted,
+        SplMintWhitelisted, SwapProgramUpdated, TreasuryUpdated, TssUpdateScheduled,
+        WhitelistEntryUpdated, WhitelistEntryUpgraded, WithdrawalCancelled, EVENT_SCHEMA_VERSION,
+    },
+    state::{InstructionId, RateLimit, WhitelistEntry},
     utils::{
-        recover_and_verify_eth_address, validate_message_hash, verify_and_update_nonce,
-        verify_authority,
+        migrate_legacy_pda, migrate_legacy_whitelist_entry, recover_and_verify_eth_address,
+        release_rate_limit, schedule_tss_update, validate_message_hash, verify_and_update_nonce,
+        verify_authority, verify_custody_balance, verify_mint_extensions, verify_multisig_disabled,
+        DEPOSIT_FEE, MAX_DEPOSIT_FEE, PDA_VERSION, WHITELIST_ENTRY_VERSION,
     },
     Pda,
 };
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{close_account, transfer_checked, CloseAccount, TransferChecked};
 
 // Initializes the gateway PDA.
 pub fn initialize(ctx: Context<Initialize>, tss_address: [u8; 20], chain_id: u64) -> Result<()> {
@@ -21,6 +37,17 @@ pub fn initialize(ctx: Context<Initialize>, tss_address: [u8; 20], chain_id: u64
         authority: ctx.accounts.signer.key(),
         chain_id,
         deposit_paused: false,
+        deposit_fee: DEPOSIT_FEE,
+        spl_deposit_fee: DEPOSIT_FEE,
+        collected_fees: 0,
+        treasury: ctx.accounts.signer.key(),
+        pending_tss_address: [0u8; 20],
+        tss_activation_slot: 0,
+        tss_overlap_end_slot: 0,
+        version: PDA_VERSION,
+        min_deposit: 0,
+        swap_program: Pubkey::default(),
+        reserved: [0u8; 88],
     };
 
     msg!(
@@ -30,23 +57,82 @@ pub fn initialize(ctx: Context<Initialize>, tss_address: [u8; 20], chain_id: u64
         tss_address
     );
 
+    emit_cpi!(GatewayInitialized {
+        version: EVENT_SCHEMA_VERSION,
+        authority: ctx.accounts.signer.key(),
+        tss_address,
+        chain_id,
+    });
+
+    Ok(())
+}
+
+// Migrates the gateway PDA from the unversioned layout, reallocating it to the current size.
+// Caller is authority stored in PDA, who tops up rent if needed.
+pub fn upgrade_pda(ctx: Context<UpgradePda>) -> Result<()> {
+    let pda_info = ctx.accounts.pda.to_account_info();
+    let pda = migrate_legacy_pda(&pda_info.try_borrow_data()?)?;
+    require!(
+        pda.authority == ctx.accounts.signer.key(),
+        Errors::SignerIsNotAuthority
+    );
+
+    let new_len = 8 + Pda::INIT_SPACE;
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required_lamports.saturating_sub(pda_info.lamports());
+    if shortfall > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.signer.to_account_info(),
+                to: pda_info.clone(),
+            },
+        );
+        system_program::transfer(cpi_context, shortfall)?;
+    }
+
+    pda_info.realloc(new_len, true)?;
+    pda.try_serialize(&mut &mut pda_info.try_borrow_mut_data()?[..])?;
+
+    msg!("PDA upgraded: version = {}", pda.version);
+
+    emit_cpi!(PdaUpgraded {
+        version: EVENT_SCHEMA_VERSION,
+        pda_version: pda.version,
+    });
+
     Ok(())
 }
 
-// Updates the TSS address. Caller is authority stored in PDA.
-pub fn update_tss(ctx: Context<UpdateTss>, tss_address: [u8; 20]) -> Result<()> {
+// Schedules a TSS rotation. Caller is authority stored in PDA.
+pub fn update_tss(
+    ctx: Context<UpdateTss>,
+    tss_address: [u8; 20],
+    activation_slot: u64,
+    overlap_slots: u64,
+) -> Result<()> {
     verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    verify_multisig_disabled(&ctx.accounts.admin_config)?;
     let pda = &mut ctx.accounts.pda;
-    pda.tss_address = tss_address;
-    pda.nonce = 0;
+    schedule_tss_update(pda, tss_address, activation_slot, overlap_slots)?;
 
     msg!(
-        "TSS address updated: new TSS address = {:?}, new nonce = {}, PDA authority = {}",
+        "TSS rotation scheduled: pending TSS address = {:?}, activation slot = {}, overlap end slot = {}, PDA authority = {}",
         tss_address,
-        pda.nonce,
+        pda.tss_activation_slot,
+        pda.tss_overlap_end_slot,
         ctx.accounts.signer.key()
     );
 
+    emit_cpi!(TssUpdateScheduled {
+        version: EVENT_SCHEMA_VERSION,
+        tss_address: pda.tss_address,
+        pending_tss_address: tss_address,
+        activation_slot: pda.tss_activation_slot,
+        overlap_end_slot: pda.tss_overlap_end_slot,
+        nonce: pda.nonce,
+    });
+
     Ok(())
 }
 
@@ -56,6 +142,7 @@ pub fn update_authority(
     new_authority_address: Pubkey,
 ) -> Result<()> {
     verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    verify_multisig_disabled(&ctx.accounts.admin_config)?;
     let pda = &mut ctx.accounts.pda;
     pda.authority = new_authority_address;
 
@@ -65,6 +152,12 @@ pub fn update_authority(
         ctx.accounts.signer.key()
     );
 
+    emit_cpi!(AuthorityUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        previous_authority: ctx.accounts.signer.key(),
+        authority: new_authority_address,
+    });
+
     Ok(())
 }
 
@@ -76,6 +169,178 @@ pub fn set_deposit_paused(ctx: Context<UpdatePaused>, deposit_paused: bool) -> R
     pda.deposit_paused = deposit_paused;
 
     msg!("Set deposit paused: {:?}", deposit_paused);
+
+    emit_cpi!(DepositPausedUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        deposit_paused,
+    });
+
+    Ok(())
+}
+
+// Updates the deposit fees. Caller is TSS or authority stored in PDA.
+pub fn update_deposit_fee(
+    ctx: Context<UpdateDepositFee>,
+    deposit_fee: u64,
+    spl_deposit_fee: u64,
+    signature: [u8; 64],
+    recovery_id: u8,
+    message_hash: [u8; 32],
+    nonce: u64,
+) -> Result<()> {
+    let pda = &mut ctx.accounts.pda;
+    require!(
+        deposit_fee <= MAX_DEPOSIT_FEE && spl_deposit_fee <= MAX_DEPOSIT_FEE,
+        Errors::DepositFeeTooHigh
+    );
+
+    // If signature is not zero, verify the signature is valid and signed by TSS
+    if signature != [0u8; 64] {
+        verify_and_update_nonce(pda, nonce)?;
+
+        validate_message_hash(
+            InstructionId::UpdateDepositFee,
+            pda.chain_id,
+            nonce,
+            None,
+            &[&deposit_fee.to_be_bytes(), &spl_deposit_fee.to_be_bytes()],
+            &message_hash,
+        )?;
+
+        recover_and_verify_eth_address(pda, &message_hash, recovery_id, &signature)?;
+    } else {
+        // If signature is zero, authority must sign the transaction
+        verify_authority(&ctx.accounts.signer.key(), pda)?;
+    }
+
+    pda.deposit_fee = deposit_fee;
+    pda.spl_deposit_fee = spl_deposit_fee;
+
+    msg!(
+        "Deposit fee updated: SOL fee = {}, SPL fee = {}",
+        deposit_fee,
+        spl_deposit_fee
+    );
+
+    emit_cpi!(DepositFeeUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        deposit_fee,
+        spl_deposit_fee,
+    });
+
+    Ok(())
+}
+
+// Updates the minimum SOL deposit. Caller is authority stored in PDA.
+pub fn update_min_deposit(ctx: Context<UpdateMinDeposit>, min_deposit: u64) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    ctx.accounts.pda.min_deposit = min_deposit;
+
+    msg!("Minimum SOL deposit updated: min deposit = {}", min_deposit);
+
+    emit_cpi!(MinDepositUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        min_deposit,
+    });
+
+    Ok(())
+}
+
+// Updates the swap program used by deposits with swap. Caller is authority stored in PDA.
+pub fn update_swap_program(ctx: Context<UpdateSwapProgram>, swap_program: Pubkey) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    ctx.accounts.pda.swap_program = swap_program;
+
+    msg!("Swap program updated: swap program = {}", swap_program);
+
+    emit_cpi!(SwapProgramUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        swap_program,
+    });
+
+    Ok(())
+}
+
+// Updates the treasury address. Caller is authority stored in PDA.
+pub fn update_treasury(ctx: Context<UpdateTreasury>, treasury: Pubkey) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    let pda = &mut ctx.accounts.pda;
+    let previous_treasury = pda.treasury;
+    pda.treasury = treasury;
+
+    msg!(
+        "Treasury updated: new treasury = {}, previous treasury = {}",
+        treasury,
+        previous_treasury
+    );
+
+    emit_cpi!(TreasuryUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        previous_treasury,
+        treasury,
+    });
+
+    Ok(())
+}
+
+// Withdraws collected deposit fees to the treasury. Caller is authority stored in PDA.
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    let pda = &mut ctx.accounts.pda;
+    require!(
+        amount <= pda.collected_fees,
+        Errors::InsufficientCollectedFees
+    );
+
+    pda.collected_fees -= amount;
+    verify_custody_balance(pda, amount)?;
+    pda.sub_lamports(amount)?;
+    ctx.accounts.treasury.add_lamports(amount)?;
+
+    msg!(
+        "Fees withdrawn: amount = {}, treasury = {}, remaining fees = {}",
+        amount,
+        ctx.accounts.treasury.key(),
+        pda.collected_fees
+    );
+
+    emit_cpi!(FeesWithdrawn {
+        version: EVENT_SCHEMA_VERSION,
+        treasury: ctx.accounts.treasury.key(),
+        amount,
+        collected_fees: pda.collected_fees,
+    });
+
+    Ok(())
+}
+
+// Exempts a program depositing through CPI from deposit fees. Caller is authority stored in PDA.
+pub fn add_fee_exemption(ctx: Context<AddFeeExemption>, program_id: Pubkey) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    ctx.accounts.fee_exemption.program_id = program_id;
+
+    msg!("Fee exemption added: program = {}", program_id);
+
+    emit_cpi!(FeeExemptionAdded {
+        version: EVENT_SCHEMA_VERSION,
+        program_id,
+    });
+
+    Ok(())
+}
+
+// Removes a program's deposit fee exemption. Caller is authority stored in PDA.
+pub fn remove_fee_exemption(ctx: Context<RemoveFeeExemption>) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    let program_id = ctx.accounts.fee_exemption.program_id;
+
+    msg!("Fee exemption removed: program = {}", program_id);
+
+    emit_cpi!(FeeExemptionRemoved {
+        version: EVENT_SCHEMA_VERSION,
+        program_id,
+    });
+
     Ok(())
 }
 
@@ -113,6 +378,22 @@ pub fn whitelist_spl_mint(
         verify_authority(&authority.key(), &ctx.accounts.pda)?;
     }
 
+    // Reject Token-2022 mints with extensions the gateway cannot custody
+    verify_mint_extensions(&whitelist_candidate.to_account_info())?;
+
+    // Default the settings of a new entry, and re-enable deposits if the mint was unwhitelisted
+    let whitelist_entry = &mut ctx.accounts.whitelist_entry;
+    if whitelist_entry.token_program == Pubkey::default() {
+        whitelist_entry.min_deposit = 0;
+        whitelist_entry.max_deposit = u64::MAX;
+        whitelist_entry.deposit_paused = false;
+        whitelist_entry.deposit_fee = None;
+    }
+    whitelist_entry.decimals = whitelist_candidate.decimals;
+    whitelist_entry.token_program = *whitelist_candidate.to_account_info().owner;
+    whitelist_entry.withdraw_only = false;
+    whitelist_entry.version = WHITELIST_ENTRY_VERSION;
+
     msg!(
         "SPL token whitelisted: mint = {}, whitelist_entry = {}, authority = {}",
         whitelist_candidate.key(),
@@ -120,10 +401,62 @@ pub fn whitelist_spl_mint(
         ctx.accounts.authority.key()
     );
 
+    emit_cpi!(SplMintWhitelisted {
+        version: EVENT_SCHEMA_VERSION,
+        mint: whitelist_candidate.key(),
+        whitelist_entry: ctx.accounts.whitelist_entry.key(),
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+// Migrates a whitelist entry created before entries held per-mint settings to the current layout.
+// Caller is authority stored in PDA, who tops up rent if needed.
+pub fn upgrade_whitelist_entry(ctx: Context<UpgradeWhitelistEntry>) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+
+    let mint_account = &ctx.accounts.mint_account;
+    let entry_info = ctx.accounts.whitelist_entry.to_account_info();
+    let whitelist_entry = migrate_legacy_whitelist_entry(
+        &entry_info.try_borrow_data()?,
+        mint_account.decimals,
+        *mint_account.to_account_info().owner,
+    )?;
+
+    let new_len = 8 + WhitelistEntry::INIT_SPACE;
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required_lamports.saturating_sub(entry_info.lamports());
+    if shortfall > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.signer.to_account_info(),
+                to: entry_info.clone(),
+            },
+        );
+        system_program::transfer(cpi_context, shortfall)?;
+    }
+
+    entry_info.realloc(new_len, true)?;
+    whitelist_entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Whitelist entry upgraded: mint = {}, version = {}",
+        mint_account.key(),
+        whitelist_entry.version
+    );
+
+    emit_cpi!(WhitelistEntryUpgraded {
+        version: EVENT_SCHEMA_VERSION,
+        mint: mint_account.key(),
+        entry_version: whitelist_entry.version,
+    });
+
     Ok(())
 }
 
-// Unwhitelists an SPL token. Caller is TSS.
+// Unwhitelists an SPL token, keeping it withdraw-only. Caller is TSS.
 pub fn unwhitelist_spl_mint(
     ctx: Context<Unwhitelist>,
     signature: [u8; 64],
@@ -157,6 +490,9 @@ pub fn unwhitelist_spl_mint(
         verify_authority(&authority.key(), &ctx.accounts.pda)?;
     }
 
+    // Block deposits while TSS withdrawals keep working
+    ctx.accounts.whitelist_entry.withdraw_only = true;
+
     msg!(
         "SPL token unwhitelisted: mint = {}, whitelist_entry = {}, authority = {}",
         whitelist_candidate.key(),
@@ -164,16 +500,283 @@ pub fn unwhitelist_spl_mint(
         ctx.accounts.authority.key()
     );
 
+    emit_cpi!(SplMintUnwhitelisted {
+        version: EVENT_SCHEMA_VERSION,
+        mint: whitelist_candidate.key(),
+        whitelist_entry: ctx.accounts.whitelist_entry.key(),
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+// Updates the per-mint deposit settings of a whitelisted SPL token. Caller is authority stored in PDA.
+pub fn update_whitelist_entry(
+    ctx: Context<UpdateWhitelistEntry>,
+    min_deposit: u64,
+    max_deposit: u64,
+    deposit_paused: bool,
+    deposit_fee: Option<u64>,
+) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    require!(min_deposit <= max_deposit, Errors::InvalidWhitelistConfig);
+    require!(
+        deposit_fee.unwrap_or_default() <= MAX_DEPOSIT_FEE,
+        Errors::DepositFeeTooHigh
+    );
+
+    let whitelist_entry = &mut ctx.accounts.whitelist_entry;
+    whitelist_entry.min_deposit = min_deposit;
+    whitelist_entry.max_deposit = max_deposit;
+    whitelist_entry.deposit_paused = deposit_paused;
+    whitelist_entry.deposit_fee = deposit_fee;
+
+    msg!(
+        "Whitelist entry updated: mint = {}, min deposit = {}, max deposit = {}, deposit paused = {}",
+        ctx.accounts.mint_account.key(),
+        min_deposit,
+        max_deposit,
+        deposit_paused
+    );
+
+    emit_cpi!(WhitelistEntryUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        mint: ctx.accounts.mint_account.key(),
+        min_deposit,
+        max_deposit,
+        deposit_paused,
+        deposit_fee,
+    });
+
+    Ok(())
+}
+
+// Sweeps the gateway ATA of a withdraw-only mint to the treasury and closes it.
+// Caller is authority stored in PDA.
+pub fn close_mint_vault(ctx: Context<CloseMintVault>) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    require!(
+        ctx.accounts.whitelist_entry.withdraw_only,
+        Errors::MintNotWithdrawOnly
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"meta", &[ctx.bumps.pda]]];
+    let amount = ctx.accounts.pda_ata.amount;
+
+    if amount > 0 {
+        let xfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.pda_ata.to_account_info(),
+                mint: ctx.accounts.mint_account.to_account_info(),
+                to: ctx.accounts.treasury_ata.to_account_info(),
+                authority: ctx.accounts.pda.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(xfer_ctx, amount, ctx.accounts.mint_account.decimals)?;
+    }
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.pda_ata.to_account_info(),
+            destination: ctx.accounts.signer.to_account_info(),
+            authority: ctx.accounts.pda.to_account_info(),
+        },
+        signer_seeds,
+    );
+    close_account(close_ctx)?;
+
+    msg!(
+        "Mint vault closed: mint = {}, swept amount = {}, treasury ATA = {}",
+        ctx.accounts.mint_account.key(),
+        amount,
+        ctx.accounts.treasury_ata.key()
+    );
+
+    emit_cpi!(MintVaultClosed {
+        version: EVENT_SCHEMA_VERSION,
+        mint: ctx.accounts.mint_account.key(),
+        treasury_ata: ctx.accounts.treasury_ata.key(),
+        amount,
+    });
+
     Ok(())
 }
 
 // Resets the PDA authority. Caller is authority stored in PDA.
 pub fn reset_nonce(ctx: Context<ResetNonce>, new_nonce: u64) -> Result<()> {
     verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    verify_multisig_disabled(&ctx.accounts.admin_config)?;
     let pda = &mut ctx.accounts.pda;
+    let previous_nonce = pda.nonce;
     pda.nonce = new_nonce;
 
     msg!("PDA nonce reset: new nonce = {}", new_nonce);
 
+    emit_cpi!(NonceReset {
+        version: EVENT_SCHEMA_VERSION,
+        previous_nonce,
+        nonce: new_nonce,
+    });
+
+    Ok(())
+}
+
+// Sets an outbound rate limit for SOL (`Pubkey::default()`) or an SPL token. Caller is authority stored in PDA.
+pub fn set_rate_limit(
+    ctx: Context<SetRateLimit>,
+    mint: Pubkey,
+    max_per_withdrawal: u64,
+    max_per_window: u64,
+    window_slots: u64,
+    delay_threshold: u64,
+    delay_slots: u64,
+) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    let rate_limit = &mut ctx.accounts.rate_limit;
+    rate_limit.mint = mint;
+    rate_limit.window_amount = 0;
+    rate_limit.last_update_slot = Clock::get()?.slot;
+    configure_rate_limit(
+        rate_limit,
+        max_per_withdrawal,
+        max_per_window,
+        window_slots,
+        delay_threshold,
+        delay_slots,
+    )?;
+
+    msg!(
+        "Rate limit set: mint = {}, max per withdrawal = {}, max per window = {}, window slots = {}, delay threshold = {}, delay slots = {}",
+        mint,
+        max_per_withdrawal,
+        max_per_window,
+        window_slots,
+        delay_threshold,
+        delay_slots
+    );
+
+    emit_cpi!(RateLimitSet {
+        version: EVENT_SCHEMA_VERSION,
+        mint,
+        max_per_withdrawal,
+        max_per_window,
+        window_slots,
+        delay_threshold,
+        delay_slots,
+    });
+
+    Ok(())
+}
+
+// Updates an outbound rate limit, keeping the amount already counted in the window.
+// Caller is authority stored in PDA.
+pub fn update_rate_limit(
+    ctx: Context<UpdateRateLimit>,
+    mint: Pubkey,
+    max_per_withdrawal: u64,
+    max_per_window: u64,
+    window_slots: u64,
+    delay_threshold: u64,
+    delay_slots: u64,
+) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    configure_rate_limit(
+        &mut ctx.accounts.rate_limit,
+        max_per_withdrawal,
+        max_per_window,
+        window_slots,
+        delay_threshold,
+        delay_slots,
+    )?;
+
+    msg!(
+        "Rate limit updated: mint = {}, max per withdrawal = {}, max per window = {}, window slots = {}, delay threshold = {}, delay slots = {}",
+        mint,
+        max_per_withdrawal,
+        max_per_window,
+        window_slots,
+        delay_threshold,
+        delay_slots
+    );
+
+    emit_cpi!(RateLimitSet {
+        version: EVENT_SCHEMA_VERSION,
+        mint,
+        max_per_withdrawal,
+        max_per_window,
+        window_slots,
+        delay_threshold,
+        delay_slots,
+    });
+
+    Ok(())
+}
+
+// Removes an outbound rate limit. Caller is authority stored in PDA.
+pub fn remove_rate_limit(ctx: Context<RemoveRateLimit>, mint: Pubkey) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+
+    msg!("Rate limit removed: mint = {}", mint);
+
+    emit_cpi!(RateLimitRemoved {
+        version: EVENT_SCHEMA_VERSION,
+        mint,
+    });
+
+    Ok(())
+}
+
+// Validates and stores rate limit parameters.
+fn configure_rate_limit(
+    rate_limit: &mut RateLimit,
+    max_per_withdrawal: u64,
+    max_per_window: u64,
+    window_slots: u64,
+    delay_threshold: u64,
+    delay_slots: u64,
+) -> Result<()> {
+    require!(
+        window_slots > 0 && max_per_withdrawal <= max_per_window,
+        Errors::InvalidRateLimit
+    );
+    rate_limit.max_per_withdrawal = max_per_withdrawal;
+    rate_limit.max_per_window = max_per_window;
+    rate_limit.window_slots = window_slots;
+    rate_limit.delay_threshold = delay_threshold;
+    rate_limit.delay_slots = delay_slots;
+    Ok(())
+}
+
+// Cancels a queued withdrawal, keeping the funds in the gateway. Caller is authority stored in PDA.
+// The nonce consumed by the withdrawal is not reused.
+pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>, _message_hash: [u8; 32]) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    let pending = &ctx.accounts.pending_withdrawal;
+
+    // Give back the amount the withdrawal consumed from the rate limit window
+    release_rate_limit(
+        &mut ctx.accounts.rate_limit,
+        pending.amount,
+        Clock::get()?.slot,
+    );
+
+    msg!(
+        "Queued withdraw cancelled: nonce = {}, amount = {}, recipient = {}",
+        pending.nonce,
+        pending.amount,
+        pending.recipient
+    );
+
+    emit_cpi!(WithdrawalCancelled {
+        version: EVENT_SCHEMA_VERSION,
+        nonce: pending.nonce,
+        recipient: pending.recipient,
+        amount: pending.amount,
+        mint: pending.mint,
+    });
+
     Ok(())
 }
@@ -1,30 +1,98 @@
 use crate::{
-    contexts::{Call, Deposit, DepositSplToken},
+    contexts::{
+        Call, Deposit, DepositMulti, DepositSolWithSwap, DepositSplToken, DepositWithSwap,
+        DepositWsol,
+    },
     errors::Errors,
-    state::RevertOptions,
-    utils::verify_payload_size,
+    events::{
+        Called, CalledV2, Deposited, DepositedAndCalled, DepositedAndCalledV2, DepositedMulti,
+        DepositedV2, SwappedForDeposit, EVENT_SCHEMA_VERSION,
+    },
+    state::{DepositAsset, Pda, Receiver, RevertOptions, WhitelistEntry},
+    utils::{
+        resolve_deposit_fee, verify_ata_match, verify_min_deposit, verify_payload_size,
+        verify_receiver, verify_swap_output, DEPOSIT_ASSET_ACCOUNTS, MAX_DEPOSIT_ASSETS,
+    },
 };
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke};
 use anchor_lang::system_program;
-use anchor_spl::associated_token::get_associated_token_address;
-use anchor_spl::token::transfer;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
 
 // Deposits SOL into the program and credits the `receiver` on ZetaChain zEVM.
 pub fn handle_sol(
-    ctx: Context<Deposit>,
+    mut ctx: Context<Deposit>,
     amount: u64,
     receiver: [u8; 20],
     revert_options: Option<RevertOptions>,
-    deposit_fee: u64,
 ) -> Result<()> {
+    require!(receiver != [0u8; 20], Errors::EmptyReceiver);
     verify_payload_size(None, &revert_options)?;
 
-    let pda = &mut ctx.accounts.pda;
-    require!(!pda.deposit_paused, Errors::DepositPaused);
+    let deposit_fee = transfer_sol(&mut ctx, amount)?;
+
+    emit_cpi!(Deposited {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount,
+        fee: deposit_fee,
+        mint: None,
+        revert_options,
+    });
+
+    Ok(())
+}
+
+// Deposits SOL and calls a contract on ZetaChain zEVM.
+pub fn handle_sol_with_call(
+    mut ctx: Context<Deposit>,
+    amount: u64,
+    receiver: [u8; 20],
+    message: Vec<u8>,
+    revert_options: Option<RevertOptions>,
+) -> Result<()> {
     require!(receiver != [0u8; 20], Errors::EmptyReceiver);
+    verify_payload_size(Some(&message), &revert_options)?;
+
+    let deposit_fee = transfer_sol(&mut ctx, amount)?;
 
-    let amount_with_fees = amount + deposit_fee;
+    emit_cpi!(DepositedAndCalled {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount,
+        fee: deposit_fee,
+        mint: None,
+        message,
+        revert_options,
+    });
+
+    Ok(())
+}
+
+// Transfers the SOL amount and deposit fee from the signer to the gateway PDA.
+// Returns the deposit fee charged, which is added to the collected fees.
+fn transfer_sol(ctx: &mut Context<Deposit>, amount: u64) -> Result<u64> {
+    let pda = &ctx.accounts.pda;
+    require!(!pda.deposit_paused, Errors::DepositPaused);
+    verify_min_deposit(amount, pda.min_deposit)?;
+
+    let deposit_fee = resolve_deposit_fee(
+        pda.deposit_fee,
+        ctx.accounts.fee_exemption.as_ref(),
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+
+    let amount_with_fees = amount
+        .checked_add(deposit_fee)
+        .ok_or(Errors::ArithmeticOverflow)?;
     let cpi_context = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
         system_program::Transfer {
@@ -34,62 +102,200 @@ pub fn handle_sol(
     );
     system_program::transfer(cpi_context, amount_with_fees)?;
 
-    msg!(
-        "Deposit executed: amount = {}, fee = {}, receiver = {:?}, pda = {}, revert options = {:?}",
-        amount,
-        deposit_fee,
+    let pda = &mut ctx.accounts.pda;
+    pda.collected_fees = pda
+        .collected_fees
+        .checked_add(deposit_fee)
+        .ok_or(Errors::ArithmeticOverflow)?;
+
+    Ok(deposit_fee)
+}
+
+// Unwraps a wrapped SOL account of the signer and credits the SOL to the `receiver` on ZetaChain zEVM.
+pub fn handle_wsol(
+    mut ctx: Context<DepositWsol>,
+    receiver: [u8; 20],
+    revert_options: Option<RevertOptions>,
+) -> Result<()> {
+    require!(receiver != [0u8; 20], Errors::EmptyReceiver);
+    verify_payload_size(None, &revert_options)?;
+
+    let (deposit_fee, amount) = unwrap_wsol(&mut ctx)?;
+
+    emit_cpi!(Deposited {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
         receiver,
-        ctx.accounts.pda.key(),
-        revert_options
-    );
+        amount,
+        fee: deposit_fee,
+        mint: None,
+        revert_options,
+    });
 
     Ok(())
 }
 
-// Deposits SOL and calls a contract on ZetaChain zEVM.
-pub fn handle_sol_with_call(
-    ctx: Context<Deposit>,
-    amount: u64,
+// Unwraps a wrapped SOL account of the signer, deposits the SOL and calls a contract on ZetaChain zEVM.
+pub fn handle_wsol_with_call(
+    mut ctx: Context<DepositWsol>,
     receiver: [u8; 20],
     message: Vec<u8>,
     revert_options: Option<RevertOptions>,
-    deposit_fee: u64,
 ) -> Result<()> {
+    require!(receiver != [0u8; 20], Errors::EmptyReceiver);
     verify_payload_size(Some(&message), &revert_options)?;
 
-    handle_sol(ctx, amount, receiver, revert_options, deposit_fee)?;
+    let (deposit_fee, amount) = unwrap_wsol(&mut ctx)?;
 
-    msg!("Deposit and call executed with message = {:?}", message);
+    emit_cpi!(DepositedAndCalled {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount,
+        fee: deposit_fee,
+        mint: None,
+        message,
+        revert_options,
+    });
 
     Ok(())
 }
 
+// Closes the wrapped SOL account to the signer, which unwraps its whole balance and refunds the rent,
+// then transfers the unwrapped SOL and the deposit fee to the gateway PDA.
+// Returns the deposit fee charged and the amount of SOL deposited.
+fn unwrap_wsol(ctx: &mut Context<DepositWsol>) -> Result<(u64, u64)> {
+    require_keys_eq!(
+        ctx.accounts.wsol_account.mint,
+        native_mint::ID,
+        Errors::NotNativeMint
+    );
+
+    let pda = &ctx.accounts.pda;
+    require!(!pda.deposit_paused, Errors::DepositPaused);
+    let amount = ctx.accounts.wsol_account.amount;
+    verify_min_deposit(amount, pda.min_deposit)?;
+    let deposit_fee = resolve_deposit_fee(
+        pda.deposit_fee,
+        ctx.accounts.fee_exemption.as_ref(),
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+
+    close_account(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.wsol_account.to_account_info(),
+            destination: ctx.accounts.signer.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
+        },
+    ))?;
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.signer.to_account_info(),
+            to: ctx.accounts.pda.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_context, amount)?;
+
+    charge_deposit_fee(
+        &ctx.accounts.signer,
+        &mut ctx.accounts.pda,
+        &ctx.accounts.system_program,
+        deposit_fee,
+    )?;
+
+    Ok((deposit_fee, amount))
+}
+
 // Deposits SPL tokens and credits the `receiver` on ZetaChain zEVM.
 pub fn handle_spl(
-    ctx: Context<DepositSplToken>,
+    mut ctx: Context<DepositSplToken>,
     amount: u64,
     receiver: [u8; 20],
     revert_options: Option<RevertOptions>,
-    deposit_fee: u64,
 ) -> Result<()> {
+    require!(receiver != [0u8; 20], Errors::EmptyReceiver);
     verify_payload_size(None, &revert_options)?;
+
+    let (deposit_fee, received_amount) = transfer_spl(&mut ctx, amount)?;
+
+    emit_cpi!(Deposited {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount: received_amount,
+        fee: deposit_fee,
+        mint: Some(ctx.accounts.mint_account.key()),
+        revert_options,
+    });
+
+    Ok(())
+}
+
+// Deposits SPL tokens and calls a contract on ZetaChain zEVM.
+pub fn handle_spl_with_call(
+    mut ctx: Context<DepositSplToken>,
+    amount: u64,
+    receiver: [u8; 20],
+    message: Vec<u8>,
+    revert_options: Option<RevertOptions>,
+) -> Result<()> {
+    require!(receiver != [0u8; 20], Errors::EmptyReceiver);
+    verify_payload_size(Some(&message), &revert_options)?;
+
+    let (deposit_fee, received_amount) = transfer_spl(&mut ctx, amount)?;
+
+    emit_cpi!(DepositedAndCalled {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount: received_amount,
+        fee: deposit_fee,
+        mint: Some(ctx.accounts.mint_account.key()),
+        message,
+        revert_options,
+    });
+
+    Ok(())
+}
+
+// Transfers the deposit fee in SOL and the SPL token amount from the signer to the gateway PDA.
+// Returns the deposit fee charged, which is added to the collected fees, and the amount received
+// by the gateway, measured as the balance delta of the PDA ATA so that any fee withheld on transfer
+// is never credited.
+fn transfer_spl(ctx: &mut Context<DepositSplToken>, amount: u64) -> Result<(u64, u64)> {
     let token = &ctx.accounts.token_program;
     let from = &ctx.accounts.from;
 
-    let pda = &mut ctx.accounts.pda;
-    require!(!pda.deposit_paused, Errors::DepositPaused);
-    require!(receiver != [0u8; 20], Errors::EmptyReceiver);
+    verify_spl_deposit(
+        &ctx.accounts.pda,
+        &ctx.accounts.whitelist_entry,
+        &token.key(),
+        amount,
+    )?;
 
-    let cpi_context = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-            from: ctx.accounts.signer.to_account_info().clone(),
-            to: pda.to_account_info().clone(),
-        },
-    );
-    system_program::transfer(cpi_context, deposit_fee)?;
+    let deposit_fee = resolve_deposit_fee(
+        ctx.accounts
+            .whitelist_entry
+            .deposit_fee
+            .unwrap_or(ctx.accounts.pda.spl_deposit_fee),
+        ctx.accounts.fee_exemption.as_ref(),
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    charge_deposit_fee(
+        &ctx.accounts.signer,
+        &mut ctx.accounts.pda,
+        &ctx.accounts.system_program,
+        deposit_fee,
+    )?;
 
-    let pda_ata = get_associated_token_address(&ctx.accounts.pda.key(), &from.mint);
+    let pda_ata = get_associated_token_address_with_program_id(
+        &ctx.accounts.pda.key(),
+        &from.mint,
+        &token.key(),
+    );
     require!(
         pda_ata == ctx.accounts.to.to_account_info().key(),
         Errors::DepositToAddressMismatch
@@ -97,48 +303,423 @@ pub fn handle_spl(
 
     let xfer_ctx = CpiContext::new(
         token.to_account_info(),
-        anchor_spl::token::Transfer {
+        TransferChecked {
             from: ctx.accounts.from.to_account_info(),
+            mint: ctx.accounts.mint_account.to_account_info(),
             to: ctx.accounts.to.to_account_info(),
             authority: ctx.accounts.signer.to_account_info(),
         },
     );
-    transfer(xfer_ctx, amount)?;
+    let balance_before = ctx.accounts.to.amount;
+    transfer_checked(xfer_ctx, amount, ctx.accounts.mint_account.decimals)?;
 
-    msg!(
-            "Deposit SPL executed: amount = {}, fee = {}, receiver = {:?}, pda = {}, mint = {}, revert options = {:?}",
-            amount,
-            deposit_fee,
-            receiver,
-            ctx.accounts.pda.key(),
-            ctx.accounts.mint_account.key(),
-            revert_options
+    ctx.accounts.to.reload()?;
+    let received_amount = ctx
+        .accounts
+        .to
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(Errors::DepositBalanceMismatch)?;
+
+    Ok((deposit_fee, received_amount))
+}
+
+// Deposits SOL and several SPL tokens as a single inbound charged a single deposit fee, and credits
+// the `receiver` on ZetaChain zEVM, calling it with `message` if provided.
+// The fee is the highest of the SOL deposit fee and the deposit fees of the SPL token mints.
+// Each SPL token asset is described by the remaining accounts
+// [whitelist entry, mint, source token account, gateway ATA, token program], in the order of `assets`.
+pub fn handle_multi<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DepositMulti<'info>>,
+    sol_amount: u64,
+    assets: Vec<DepositAsset>,
+    receiver: [u8; 20],
+    message: Option<Vec<u8>>,
+    revert_options: Option<RevertOptions>,
+) -> Result<()> {
+    require!(receiver != [0u8; 20], Errors::EmptyReceiver);
+    verify_payload_size(message.as_ref(), &revert_options)?;
+    require!(
+        assets.len() <= MAX_DEPOSIT_ASSETS,
+        Errors::InvalidDepositAssets
+    );
+    require!(
+        ctx.remaining_accounts.len() == assets.len() * DEPOSIT_ASSET_ACCOUNTS,
+        Errors::InvalidDepositAssets
+    );
+
+    let pda = &ctx.accounts.pda;
+    require!(!pda.deposit_paused, Errors::DepositPaused);
+    let mut base_fee = 0;
+    if sol_amount > 0 || assets.is_empty() {
+        verify_min_deposit(sol_amount, pda.min_deposit)?;
+        base_fee = pda.deposit_fee;
+    }
+
+    // 1. Transfer SOL
+    if sol_amount > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.signer.to_account_info(),
+                to: ctx.accounts.pda.to_account_info(),
+            },
         );
+        system_program::transfer(cpi_context, sol_amount)?;
+    }
+
+    // 2. Transfer each SPL token asset, crediting the amount received by the gateway
+    let mut deposited_assets = Vec::with_capacity(assets.len());
+    for (i, asset) in assets.iter().enumerate() {
+        require!(
+            !assets[..i]
+                .iter()
+                .any(|previous| previous.mint == asset.mint),
+            Errors::InvalidDepositAssets
+        );
+        let accounts =
+            &ctx.remaining_accounts[i * DEPOSIT_ASSET_ACCOUNTS..(i + 1) * DEPOSIT_ASSET_ACCOUNTS];
+        let (asset_fee, amount) =
+            transfer_spl_asset(&ctx.accounts.signer, &ctx.accounts.pda, accounts, asset)?;
+        base_fee = base_fee.max(asset_fee);
+        deposited_assets.push(DepositAsset {
+            mint: asset.mint,
+            amount,
+        });
+    }
+
+    // 3. Charge the single deposit fee, the highest fee among the deposited assets
+    let deposit_fee = resolve_deposit_fee(
+        base_fee,
+        ctx.accounts.fee_exemption.as_ref(),
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    charge_deposit_fee(
+        &ctx.accounts.signer,
+        &mut ctx.accounts.pda,
+        &ctx.accounts.system_program,
+        deposit_fee,
+    )?;
+
+    emit_cpi!(DepositedMulti {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        sol_amount,
+        assets: deposited_assets,
+        fee: deposit_fee,
+        message,
+        revert_options,
+    });
 
     Ok(())
 }
 
-// Deposits SPL tokens and calls a contract on ZetaChain zEVM.
-pub fn handle_spl_with_call(
-    ctx: Context<DepositSplToken>,
+// Transfers an SPL token asset of a multi-asset deposit from the signer to the gateway PDA, after
+// validating its remaining accounts. Returns the deposit fee of the mint and the amount received
+// by the gateway, measured as the balance delta of the PDA ATA.
+fn transfer_spl_asset<'info>(
+    signer: &Signer<'info>,
+    pda: &Account<'info, Pda>,
+    accounts: &'info [AccountInfo<'info>],
+    asset: &DepositAsset,
+) -> Result<(u64, u64)> {
+    let [whitelist_entry, mint_account, from, to, token_program] = accounts else {
+        return err!(Errors::InvalidDepositAssets);
+    };
+
+    let (whitelist_entry_address, _) =
+        Pubkey::find_program_address(&[b"whitelist", asset.mint.as_ref()], &crate::ID);
+    require_keys_eq!(
+        whitelist_entry.key(),
+        whitelist_entry_address,
+        Errors::InvalidDepositAssets
+    );
+    require_keys_eq!(mint_account.key(), asset.mint, Errors::InvalidDepositAssets);
+    let whitelist_entry = Account::<WhitelistEntry>::try_from(whitelist_entry)?;
+    let mint = InterfaceAccount::<Mint>::try_from(mint_account)?;
+    let token_program = Interface::<TokenInterface>::try_from(token_program)?;
+
+    verify_spl_deposit(pda, &whitelist_entry, &token_program.key(), asset.amount)?;
+    verify_ata_match(&pda.key(), &asset.mint, &token_program.key(), &to.key())?;
+
+    let balance_before = InterfaceAccount::<TokenAccount>::try_from(to)?.amount;
+    let xfer_ctx = CpiContext::new(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: from.clone(),
+            mint: mint_account.clone(),
+            to: to.clone(),
+            authority: signer.to_account_info(),
+        },
+    );
+    transfer_checked(xfer_ctx, asset.amount, mint.decimals)?;
+
+    let balance_after = InterfaceAccount::<TokenAccount>::try_from(to)?.amount;
+    let received_amount = balance_after
+        .checked_sub(balance_before)
+        .ok_or(Errors::DepositBalanceMismatch)?;
+    let deposit_fee = whitelist_entry.deposit_fee.unwrap_or(pda.spl_deposit_fee);
+
+    Ok((deposit_fee, received_amount))
+}
+
+// Verifies an SPL token deposit against the gateway state and the per-mint settings of the whitelist entry.
+fn verify_spl_deposit(
+    pda: &Pda,
+    whitelist_entry: &WhitelistEntry,
+    token_program: &Pubkey,
     amount: u64,
+) -> Result<()> {
+    require!(!pda.deposit_paused, Errors::DepositPaused);
+    require!(!whitelist_entry.withdraw_only, Errors::MintWithdrawOnly);
+    require!(!whitelist_entry.deposit_paused, Errors::DepositPaused);
+    verify_min_deposit(amount, whitelist_entry.min_deposit)?;
+    require!(
+        amount <= whitelist_entry.max_deposit,
+        Errors::DepositAboveMaximum
+    );
+    require!(
+        *token_program == whitelist_entry.token_program,
+        Errors::InvalidTokenProgram
+    );
+
+    Ok(())
+}
+
+// Transfers the deposit fee in SOL from the signer to the gateway PDA and adds it to the collected fees.
+fn charge_deposit_fee<'info>(
+    signer: &Signer<'info>,
+    pda: &mut Account<'info, Pda>,
+    system_program: &Program<'info, System>,
+    deposit_fee: u64,
+) -> Result<()> {
+    let cpi_context = CpiContext::new(
+        system_program.to_account_info(),
+        system_program::Transfer {
+            from: signer.to_account_info(),
+            to: pda.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_context, deposit_fee)?;
+
+    pda.collected_fees = pda
+        .collected_fees
+        .checked_add(deposit_fee)
+        .ok_or(Errors::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+// Invokes the swap program with the remaining accounts, forwarding the signer privileges of the transaction.
+// The gateway program cannot be forwarded, so the swap cannot reenter the gateway and have a nested
+// deposit counted again in the swap output.
+fn invoke_swap(
+    swap_program: Pubkey,
+    remaining_accounts: &[AccountInfo],
+    swap_data: Vec<u8>,
+) -> Result<()> {
+    require!(
+        remaining_accounts
+            .iter()
+            .all(|account| account.key() != crate::ID),
+        Errors::InvalidSwapAccounts
+    );
+
+    let account_metas: Vec<AccountMeta> = remaining_accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        })
+        .collect();
+    let swap_ix = Instruction {
+        program_id: swap_program,
+        accounts: account_metas,
+        data: swap_data,
+    };
+    invoke(&swap_ix, remaining_accounts)?;
+
+    Ok(())
+}
+
+// Swaps a token through the configured swap program into a whitelisted SPL token held by the
+// gateway PDA, then credits the output amount to the `receiver` on ZetaChain zEVM.
+// The swap instruction accounts are passed as remaining accounts and its data as `swap_data`.
+pub fn handle_spl_with_swap(
+    ctx: Context<DepositWithSwap>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    swap_data: Vec<u8>,
     receiver: [u8; 20],
-    message: Vec<u8>,
     revert_options: Option<RevertOptions>,
-    deposit_fee: u64,
 ) -> Result<()> {
-    verify_payload_size(Some(&message), &revert_options)?;
+    require!(receiver != [0u8; 20], Errors::EmptyReceiver);
+    verify_payload_size(None, &revert_options)?;
+    require!(
+        ctx.accounts.pda.swap_program != Pubkey::default(),
+        Errors::InvalidSwapProgram
+    );
 
-    handle_spl(ctx, amount, receiver, revert_options, deposit_fee)?;
+    let pda_ata = get_associated_token_address_with_program_id(
+        &ctx.accounts.pda.key(),
+        &ctx.accounts.mint_account.key(),
+        &ctx.accounts.token_program.key(),
+    );
+    require!(
+        pda_ata == ctx.accounts.to.key(),
+        Errors::DepositToAddressMismatch
+    );
+
+    // Swap with the signer as authority, measuring the balances the swap is bounded by
+    let source_before = ctx.accounts.source.amount;
+    let to_before = ctx.accounts.to.amount;
 
-    msg!("Deposit SPL and call executed with message = {:?}", message);
+    invoke_swap(
+        ctx.accounts.swap_program.key(),
+        ctx.remaining_accounts,
+        swap_data,
+    )?;
+
+    ctx.accounts.source.reload()?;
+    ctx.accounts.to.reload()?;
+    let amount_spent = source_before
+        .checked_sub(ctx.accounts.source.amount)
+        .ok_or(Errors::DepositBalanceMismatch)?;
+    let amount_received = ctx
+        .accounts
+        .to
+        .amount
+        .checked_sub(to_before)
+        .ok_or(Errors::DepositBalanceMismatch)?;
+    let amount = verify_swap_output(amount_spent, amount_in, amount_received, minimum_amount_out)?;
+
+    // Deposit the swap output
+    verify_spl_deposit(
+        &ctx.accounts.pda,
+        &ctx.accounts.whitelist_entry,
+        &ctx.accounts.token_program.key(),
+        amount,
+    )?;
+    let deposit_fee = resolve_deposit_fee(
+        ctx.accounts
+            .whitelist_entry
+            .deposit_fee
+            .unwrap_or(ctx.accounts.pda.spl_deposit_fee),
+        ctx.accounts.fee_exemption.as_ref(),
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    charge_deposit_fee(
+        &ctx.accounts.signer,
+        &mut ctx.accounts.pda,
+        &ctx.accounts.system_program,
+        deposit_fee,
+    )?;
+
+    emit_cpi!(SwappedForDeposit {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        swap_program: ctx.accounts.swap_program.key(),
+        input_mint: ctx.accounts.source.mint,
+        amount_in: amount_spent,
+        output_mint: Some(ctx.accounts.mint_account.key()),
+        amount_out: amount,
+    });
+
+    emit_cpi!(Deposited {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount,
+        fee: deposit_fee,
+        mint: Some(ctx.accounts.mint_account.key()),
+        revert_options,
+    });
+
+    Ok(())
+}
+
+// Swaps a token through the configured swap program into SOL, and deposits the output
+// to credit the `receiver` on ZetaChain zEVM. The swap program pays the output to the PDA.
+pub fn handle_sol_with_swap(
+    ctx: Context<DepositSolWithSwap>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    swap_data: Vec<u8>,
+    receiver: [u8; 20],
+    revert_options: Option<RevertOptions>,
+) -> Result<()> {
+    require!(receiver != [0u8; 20], Errors::EmptyReceiver);
+    verify_payload_size(None, &revert_options)?;
+    require!(
+        ctx.accounts.pda.swap_program != Pubkey::default(),
+        Errors::InvalidSwapProgram
+    );
+
+    // Swap with the signer as authority, measuring the balances the swap is bounded by
+    let source_before = ctx.accounts.source.amount;
+    let pda_lamports_before = ctx.accounts.pda.get_lamports();
+
+    invoke_swap(
+        ctx.accounts.swap_program.key(),
+        ctx.remaining_accounts,
+        swap_data,
+    )?;
+
+    ctx.accounts.source.reload()?;
+    let amount_spent = source_before
+        .checked_sub(ctx.accounts.source.amount)
+        .ok_or(Errors::DepositBalanceMismatch)?;
+    let amount_received = ctx
+        .accounts
+        .pda
+        .get_lamports()
+        .checked_sub(pda_lamports_before)
+        .ok_or(Errors::DepositBalanceMismatch)?;
+    let amount = verify_swap_output(amount_spent, amount_in, amount_received, minimum_amount_out)?;
+
+    // Deposit the swap output as SOL
+    require!(!ctx.accounts.pda.deposit_paused, Errors::DepositPaused);
+    verify_min_deposit(amount, ctx.accounts.pda.min_deposit)?;
+    let deposit_fee = resolve_deposit_fee(
+        ctx.accounts.pda.deposit_fee,
+        ctx.accounts.fee_exemption.as_ref(),
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    charge_deposit_fee(
+        &ctx.accounts.signer,
+        &mut ctx.accounts.pda,
+        &ctx.accounts.system_program,
+        deposit_fee,
+    )?;
+
+    emit_cpi!(SwappedForDeposit {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        swap_program: ctx.accounts.swap_program.key(),
+        input_mint: ctx.accounts.source.mint,
+        amount_in: amount_spent,
+        output_mint: None,
+        amount_out: amount,
+    });
+
+    emit_cpi!(Deposited {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount,
+        fee: deposit_fee,
+        mint: None,
+        revert_options,
+    });
 
     Ok(())
 }
 
 // Calls a contract on ZetaChain zEVM.
 pub fn handle_call(
-    _ctx: Context<Call>,
+    ctx: Context<Call>,
     receiver: [u8; 20],
     message: Vec<u8>,
     revert_options: Option<RevertOptions>,
@@ -146,12 +727,138 @@ pub fn handle_call(
     require!(receiver != [0u8; 20], Errors::EmptyReceiver);
     verify_payload_size(Some(&message), &revert_options)?;
 
-    msg!(
-        "Call executed: receiver = {:?}, message = {:?}, revert options = {:?}",
+    emit_cpi!(Called {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
         receiver,
         message,
-        revert_options
-    );
+        revert_options,
+    });
+
+    Ok(())
+}
+
+// Deposits SOL into the program and credits the `receiver` on its target chain through ZetaChain.
+pub fn handle_sol_v2(
+    mut ctx: Context<Deposit>,
+    amount: u64,
+    receiver: Receiver,
+    revert_options: Option<RevertOptions>,
+) -> Result<()> {
+    verify_receiver(&receiver)?;
+    verify_payload_size(None, &revert_options)?;
+
+    let deposit_fee = transfer_sol(&mut ctx, amount)?;
+
+    emit_cpi!(DepositedV2 {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount,
+        fee: deposit_fee,
+        mint: None,
+        revert_options,
+    });
+
+    Ok(())
+}
+
+// Deposits SOL and calls the `receiver` on its target chain through ZetaChain.
+pub fn handle_sol_with_call_v2(
+    mut ctx: Context<Deposit>,
+    amount: u64,
+    receiver: Receiver,
+    message: Vec<u8>,
+    revert_options: Option<RevertOptions>,
+) -> Result<()> {
+    verify_receiver(&receiver)?;
+    verify_payload_size(Some(&message), &revert_options)?;
+
+    let deposit_fee = transfer_sol(&mut ctx, amount)?;
+
+    emit_cpi!(DepositedAndCalledV2 {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount,
+        fee: deposit_fee,
+        mint: None,
+        message,
+        revert_options,
+    });
+
+    Ok(())
+}
+
+// Deposits SPL tokens and credits the `receiver` on its target chain through ZetaChain.
+pub fn handle_spl_v2(
+    mut ctx: Context<DepositSplToken>,
+    amount: u64,
+    receiver: Receiver,
+    revert_options: Option<RevertOptions>,
+) -> Result<()> {
+    verify_receiver(&receiver)?;
+    verify_payload_size(None, &revert_options)?;
+
+    let (deposit_fee, received_amount) = transfer_spl(&mut ctx, amount)?;
+
+    emit_cpi!(DepositedV2 {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount: received_amount,
+        fee: deposit_fee,
+        mint: Some(ctx.accounts.mint_account.key()),
+        revert_options,
+    });
+
+    Ok(())
+}
+
+// Deposits SPL tokens and calls the `receiver` on its target chain through ZetaChain.
+pub fn handle_spl_with_call_v2(
+    mut ctx: Context<DepositSplToken>,
+    amount: u64,
+    receiver: Receiver,
+    message: Vec<u8>,
+    revert_options: Option<RevertOptions>,
+) -> Result<()> {
+    verify_receiver(&receiver)?;
+    verify_payload_size(Some(&message), &revert_options)?;
+
+    let (deposit_fee, received_amount) = transfer_spl(&mut ctx, amount)?;
+
+    emit_cpi!(DepositedAndCalledV2 {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        amount: received_amount,
+        fee: deposit_fee,
+        mint: Some(ctx.accounts.mint_account.key()),
+        message,
+        revert_options,
+    });
+
+    Ok(())
+}
+
+// Calls the `receiver` on its target chain through ZetaChain.
+pub fn handle_call_v2(
+    ctx: Context<Call>,
+    receiver: Receiver,
+    message: Vec<u8>,
+    revert_options: Option<RevertOptions>,
+) -> Result<()> {
+    verify_receiver(&receiver)?;
+    verify_payload_size(Some(&message), &revert_options)?;
+
+    emit_cpi!(CalledV2 {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.signer.key(),
+        receiver,
+        message,
+        revert_options,
+    });
 
     Ok(())
 }
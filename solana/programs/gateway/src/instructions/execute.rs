@@ -1,11 +1,20 @@
 use crate::{
-    contexts::{Execute, ExecuteSPLToken, IncrementNonce},
+    contexts::{Execute, ExecuteSPLToken, IncrementNonce, IncrementNonceV2},
+    errors::Errors,
+    events::{ExecuteCompleted, Executed, NonceIncremented, OutboundFailed, EVENT_SCHEMA_VERSION},
     state::CallableInstruction,
-    state::InstructionId,
-    utils::{prepare_account_metas, validate_message, verify_ata_match},
+    state::{ExpectedAccount, FailureCode, InstructionId},
+    utils::{
+        apply_rate_limit, create_recipient_ata, hash_expected_accounts, load_expected_accounts,
+        load_return_data, prepare_account_metas, validate_message, verify_ata_match,
+        verify_custody_balance, verify_expected_accounts,
+    },
 };
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{instruction::Instruction, program::invoke};
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::{get_return_data, invoke_signed},
+};
 
 // Increments nonce, used by TSS in case outbound fails.
 pub fn increment_nonce(
@@ -33,9 +42,223 @@ pub fn increment_nonce(
 
     msg!("Failure reason: {}", failure_reason);
 
+    emit_cpi!(NonceIncremented {
+        version: EVENT_SCHEMA_VERSION,
+        nonce,
+        amount,
+        failure_reason,
+    });
+
+    Ok(())
+}
+
+// Increments nonce with a typed failure code for the failed outbound identified by its original
+// instruction ID, used by TSS in case outbound fails. The amount is refunded to the revert or abort
+// address of the original message if the refund accounts are passed.
+// The original instruction ID cannot be a nonce increment.
+// The message hash commits to the original instruction ID, the failure code, and the refund address
+// and mint if any.
+#[allow(clippy::too_many_arguments)]
+pub fn increment_nonce_v2(
+    ctx: Context<IncrementNonceV2>,
+    amount: u64,
+    signature: [u8; 64],
+    recovery_id: u8,
+    message_hash: [u8; 32],
+    nonce: u64,
+    failure_code: FailureCode,
+    original_instruction_id: InstructionId,
+) -> Result<()> {
+    require!(
+        !matches!(
+            original_instruction_id,
+            InstructionId::IncrementNonce | InstructionId::IncrementNonceV2
+        ),
+        Errors::InvalidInstructionData
+    );
+    let refund_address = ctx
+        .accounts
+        .refund_address
+        .as_ref()
+        .map(|account| account.key());
+    let mint = ctx
+        .accounts
+        .mint_account
+        .as_ref()
+        .map(|account| account.key());
+    require!(
+        refund_address.is_some() || mint.is_none(),
+        Errors::InvalidRefundAccounts
+    );
+
+    // 1. Validate message
+    let failure = [original_instruction_id as u8, failure_code as u8];
+    let refund_address_bytes = refund_address.map(|address| address.to_bytes());
+    let mint_bytes = mint.map(|mint| mint.to_bytes());
+    let mut additional_data: Vec<&[u8]> = vec![&failure];
+    if let Some(refund_address_bytes) = &refund_address_bytes {
+        additional_data.push(refund_address_bytes);
+    }
+    if let Some(mint_bytes) = &mint_bytes {
+        additional_data.push(mint_bytes);
+    }
+    validate_message(
+        &mut ctx.accounts.pda,
+        InstructionId::IncrementNonceV2,
+        nonce,
+        amount,
+        &additional_data,
+        &message_hash,
+        &signature,
+        recovery_id,
+    )?;
+
+    // 2. Refund the amount to the revert or abort address
+    if refund_address.is_some() {
+        match mint {
+            None => refund_sol(&ctx, amount)?,
+            Some(_) => refund_spl(&ctx, amount)?,
+        }
+    }
+
+    msg!(
+        "Failure code: {:?}, original instruction ID: {:?}",
+        failure_code,
+        original_instruction_id
+    );
+
+    emit_cpi!(OutboundFailed {
+        version: EVENT_SCHEMA_VERSION,
+        nonce,
+        amount,
+        failure_code,
+        original_instruction_id,
+        refund_address,
+        mint,
+    });
+
     Ok(())
 }
 
+// Refunds SOL of a failed outbound to the refund address, within the SOL rate limit.
+fn refund_sol(ctx: &Context<IncrementNonceV2>, amount: u64) -> Result<()> {
+    let refund_address = ctx
+        .accounts
+        .refund_address
+        .as_ref()
+        .ok_or(Errors::InvalidRefundAccounts)?;
+    apply_refund_rate_limit(ctx.accounts.rate_limit.as_ref(), &Pubkey::default(), amount)?;
+
+    verify_custody_balance(&ctx.accounts.pda, amount)?;
+    ctx.accounts.pda.sub_lamports(amount)?;
+    refund_address.add_lamports(amount)?;
+
+    Ok(())
+}
+
+// Refunds SPL tokens of a failed outbound to the ATA of the refund address, within the mint rate limit.
+// The refund ATA is created if needed, and its rent is reimbursed to the signer.
+fn refund_spl(ctx: &Context<IncrementNonceV2>, amount: u64) -> Result<()> {
+    let (
+        Some(refund_address),
+        Some(mint_account),
+        Some(pda_ata),
+        Some(refund_ata),
+        Some(token_program),
+        Some(associated_token_program),
+    ) = (
+        &ctx.accounts.refund_address,
+        &ctx.accounts.mint_account,
+        &ctx.accounts.pda_ata,
+        &ctx.accounts.refund_ata,
+        &ctx.accounts.token_program,
+        &ctx.accounts.associated_token_program,
+    )
+    else {
+        return err!(Errors::InvalidRefundAccounts);
+    };
+    apply_refund_rate_limit(
+        ctx.accounts.rate_limit.as_ref(),
+        &mint_account.key(),
+        amount,
+    )?;
+
+    verify_ata_match(
+        &ctx.accounts.pda.key(),
+        &mint_account.key(),
+        &token_program.key(),
+        &pda_ata.key(),
+    )?;
+    verify_ata_match(
+        &refund_address.key(),
+        &mint_account.key(),
+        &token_program.key(),
+        &refund_ata.key(),
+    )?;
+
+    let cost_ata_create = create_recipient_ata(
+        &ctx.accounts.signer.to_account_info(),
+        &refund_address.to_account_info(),
+        &refund_ata.to_account_info(),
+        &mint_account.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &token_program.to_account_info(),
+        &associated_token_program.to_account_info(),
+    )?;
+    verify_custody_balance(&ctx.accounts.pda, cost_ata_create)?;
+    ctx.accounts.pda.sub_lamports(cost_ata_create)?;
+    ctx.accounts.signer.add_lamports(cost_ata_create)?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"meta", &[ctx.bumps.pda]]];
+    let xfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        anchor_spl::token_interface::TransferChecked {
+            from: pda_ata.to_account_info(),
+            mint: mint_account.to_account_info(),
+            to: refund_ata.to_account_info(),
+            authority: ctx.accounts.pda.to_account_info(),
+        },
+        signer_seeds,
+    );
+    anchor_spl::token_interface::transfer_checked(xfer_ctx, amount, mint_account.decimals)
+}
+
+// Records a refund against the rate limit PDA of the refunded asset. Refunds cannot be queued.
+fn apply_refund_rate_limit(
+    rate_limit: Option<&UncheckedAccount>,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let rate_limit = rate_limit.ok_or(Errors::InvalidRefundAccounts)?;
+    let (rate_limit_address, _) =
+        Pubkey::find_program_address(&[b"rate_limit", mint.as_ref()], &crate::ID);
+    require_keys_eq!(
+        rate_limit.key(),
+        rate_limit_address,
+        Errors::InvalidRefundAccounts
+    );
+
+    let delay_slots = apply_rate_limit(rate_limit, amount)?;
+    require!(delay_slots == 0, Errors::AmountAboveDelayThreshold);
+
+    Ok(())
+}
+
+// Hashes the accounts passed by the relayer to be forwarded to the destination program, committing to
+// their order and requested writability. The hash is computed before the forwarded account policy
+// rewrites writability from on-chain state, so the TSS can reproduce it offline.
+fn hash_forwarded_accounts(remaining_accounts: &[AccountInfo]) -> [u8; 32] {
+    let forwarded_accounts: Vec<ExpectedAccount> = remaining_accounts
+        .iter()
+        .map(|account_info| ExpectedAccount {
+            pubkey: account_info.key(),
+            is_writable: account_info.is_writable,
+        })
+        .collect();
+
+    hash_expected_accounts(&forwarded_accounts)
+}
+
 // Common implementation for SOL withdrawals
 fn handle_sol_common(
     ctx: Context<Execute>,
@@ -49,25 +272,63 @@ fn handle_sol_common(
     instruction_id: InstructionId,
     instruction_data: Vec<u8>,
 ) -> Result<()> {
+    let revert = matches!(
+        instruction_id,
+        InstructionId::ExecuteSolRevert
+            | InstructionId::ExecuteSplTokenRevert
+            | InstructionId::ExecuteSolRevertV2
+            | InstructionId::ExecuteSplTokenRevertV2
+    );
+    let commits_to_accounts = matches!(
+        instruction_id,
+        InstructionId::ExecuteSolV2
+            | InstructionId::ExecuteSplTokenV2
+            | InstructionId::ExecuteSolRevertV2
+            | InstructionId::ExecuteSplTokenRevertV2
+    );
+    let accounts_commitment =
+        commits_to_accounts.then(|| hash_forwarded_accounts(ctx.remaining_accounts));
     let pda = &mut ctx.accounts.pda;
+    let account_metas = prepare_account_metas(
+        ctx.remaining_accounts,
+        &ctx.accounts.signer,
+        pda,
+        &ctx.accounts.caller.key(),
+    )?;
+    let expected_accounts = load_expected_accounts(
+        &ctx.accounts.expected_accounts,
+        &ctx.accounts.destination_program.key(),
+    )?;
 
-    // 1. Validate message
+    // 1. Validate message, committing to the forwarded accounts for V2 instruction IDs
+    //    and to the account list published by the destination program if any
+    let destination_program = ctx.accounts.destination_program.key().to_bytes();
+    let expected_accounts_hash = expected_accounts.as_deref().map(hash_expected_accounts);
+    let mut additional_data: Vec<&[u8]> = vec![&destination_program, &sender, &data];
+    if let Some(accounts_commitment) = &accounts_commitment {
+        additional_data.push(accounts_commitment);
+    }
+    if let Some(expected_accounts_hash) = &expected_accounts_hash {
+        additional_data.push(expected_accounts_hash);
+    }
     validate_message(
         pda,
         instruction_id,
         nonce,
         amount,
-        &[
-            &ctx.accounts.destination_program.key().to_bytes(),
-            &sender,
-            &data,
-        ],
+        &additional_data,
         &message_hash,
         &signature,
         recovery_id,
     )?;
 
-    let account_metas = prepare_account_metas(ctx.remaining_accounts, &ctx.accounts.signer, pda)?;
+    // 2. Apply rate limit, executions cannot be queued
+    let delay_slots = apply_rate_limit(&ctx.accounts.rate_limit, amount)?;
+    require!(delay_slots == 0, Errors::AmountAboveDelayThreshold);
+
+    if let Some(expected_accounts) = &expected_accounts {
+        verify_expected_accounts(&account_metas, expected_accounts)?;
+    }
 
     let ix = Instruction {
         program_id: ctx.accounts.destination_program.key(),
@@ -75,20 +336,46 @@ fn handle_sol_common(
         data: instruction_data,
     };
 
-    // 2. Transfer SOL to destination program PDA
+    // 3. Transfer SOL to destination program PDA
+    verify_custody_balance(pda, amount)?;
     pda.sub_lamports(amount)?;
     ctx.accounts.destination_program_pda.add_lamports(amount)?;
 
-    // 3. Invoke destination program's function
-    invoke(&ix, ctx.remaining_accounts)?;
+    // 4. Invoke destination program's function
+    let destination_program = ctx.accounts.destination_program.key();
+    let caller_seeds: &[&[&[u8]]] =
+        &[&[b"caller", destination_program.as_ref(), &[ctx.bumps.caller]]];
+    invoke_signed(&ix, ctx.remaining_accounts, caller_seeds)?;
+    let (return_data, truncated) =
+        load_return_data(get_return_data(), &ctx.accounts.destination_program.key());
 
-    // 4. Log success
+    // 5. Log success
     msg!(
         "Execute done: destination contract = {}, amount = {}",
         ctx.accounts.destination_program.key(),
         amount,
     );
 
+    emit_cpi!(Executed {
+        version: EVENT_SCHEMA_VERSION,
+        nonce,
+        destination_program: ctx.accounts.destination_program.key(),
+        sender,
+        amount,
+        mint: None,
+        data,
+        revert,
+    });
+
+    emit_cpi!(ExecuteCompleted {
+        version: EVENT_SCHEMA_VERSION,
+        nonce,
+        destination_program: ctx.accounts.destination_program.key(),
+        revert,
+        return_data,
+        truncated,
+    });
+
     Ok(())
 }
 
@@ -102,6 +389,7 @@ pub fn handle_sol(
     recovery_id: u8,
     message_hash: [u8; 32],
     nonce: u64,
+    instruction_id: InstructionId,
 ) -> Result<()> {
     let instruction_data = CallableInstruction::OnCall {
         amount,
@@ -119,7 +407,7 @@ pub fn handle_sol(
         message_hash,
         nonce,
         sender.to_vec(),
-        InstructionId::ExecuteSol,
+        instruction_id,
         instruction_data,
     )
 }
@@ -134,6 +422,7 @@ pub fn handle_sol_revert(
     recovery_id: u8,
     message_hash: [u8; 32],
     nonce: u64,
+    instruction_id: InstructionId,
 ) -> Result<()> {
     let instruction_data = CallableInstruction::OnRevert {
         amount,
@@ -151,12 +440,13 @@ pub fn handle_sol_revert(
         message_hash,
         nonce,
         sender.to_bytes().to_vec(),
-        InstructionId::ExecuteSolRevert,
+        instruction_id,
         instruction_data,
     )
 }
 
 // Common implementation for SPL token withdrawals
+#[allow(clippy::too_many_arguments)]
 fn handle_spl_token_common(
     ctx: Context<ExecuteSPLToken>,
     decimals: u8,
@@ -170,26 +460,64 @@ fn handle_spl_token_common(
     instruction_id: InstructionId,
     instruction_data: Vec<u8>,
 ) -> Result<()> {
+    let revert = matches!(
+        instruction_id,
+        InstructionId::ExecuteSolRevert
+            | InstructionId::ExecuteSplTokenRevert
+            | InstructionId::ExecuteSolRevertV2
+            | InstructionId::ExecuteSplTokenRevertV2
+    );
+    let commits_to_accounts = matches!(
+        instruction_id,
+        InstructionId::ExecuteSolV2
+            | InstructionId::ExecuteSplTokenV2
+            | InstructionId::ExecuteSolRevertV2
+            | InstructionId::ExecuteSplTokenRevertV2
+    );
+    let accounts_commitment =
+        commits_to_accounts.then(|| hash_forwarded_accounts(ctx.remaining_accounts));
     let pda = &mut ctx.accounts.pda;
+    let account_metas = prepare_account_metas(
+        ctx.remaining_accounts,
+        &ctx.accounts.signer,
+        pda,
+        &ctx.accounts.caller.key(),
+    )?;
+    let expected_accounts = load_expected_accounts(
+        &ctx.accounts.expected_accounts,
+        &ctx.accounts.destination_program.key(),
+    )?;
 
-    // 1. Validate message
+    // 1. Validate message, committing to the forwarded accounts for V2 instruction IDs
+    //    and to the account list published by the destination program if any
+    let mint = ctx.accounts.mint_account.key().to_bytes();
+    let destination_program_pda_ata = ctx.accounts.destination_program_pda_ata.key().to_bytes();
+    let expected_accounts_hash = expected_accounts.as_deref().map(hash_expected_accounts);
+    let mut additional_data: Vec<&[u8]> = vec![&mint, &destination_program_pda_ata, &sender, &data];
+    if let Some(accounts_commitment) = &accounts_commitment {
+        additional_data.push(accounts_commitment);
+    }
+    if let Some(expected_accounts_hash) = &expected_accounts_hash {
+        additional_data.push(expected_accounts_hash);
+    }
     validate_message(
         pda,
         instruction_id,
         nonce,
         amount,
-        &[
-            &ctx.accounts.mint_account.key().to_bytes(),
-            &ctx.accounts.destination_program_pda_ata.key().to_bytes(),
-            &sender,
-            &data,
-        ],
+        &additional_data,
         &message_hash,
         &signature,
         recovery_id,
     )?;
 
-    let account_metas = prepare_account_metas(ctx.remaining_accounts, &ctx.accounts.signer, pda)?;
+    // 2. Apply rate limit, executions cannot be queued
+    let delay_slots = apply_rate_limit(&ctx.accounts.rate_limit, amount)?;
+    require!(delay_slots == 0, Errors::AmountAboveDelayThreshold);
+
+    if let Some(expected_accounts) = &expected_accounts {
+        verify_expected_accounts(&account_metas, expected_accounts)?;
+    }
 
     let ix = Instruction {
         program_id: ctx.accounts.destination_program.key(),
@@ -197,26 +525,28 @@ fn handle_spl_token_common(
         data: instruction_data,
     };
 
-    // 2. Verify token accounts
+    // 3. Verify token accounts
     verify_ata_match(
         &pda.key(),
         &ctx.accounts.mint_account.key(),
+        &ctx.accounts.token_program.key(),
         &ctx.accounts.pda_ata.key(),
     )?;
 
     verify_ata_match(
         &ctx.accounts.destination_program_pda.key(),
         &ctx.accounts.mint_account.key(),
+        &ctx.accounts.token_program.key(),
         &ctx.accounts.destination_program_pda_ata.key(),
     )?;
 
-    // 3. Transfer tokens
+    // 4. Transfer tokens
     let token = &ctx.accounts.token_program;
     let signer_seeds: &[&[&[u8]]] = &[&[b"meta", &[ctx.bumps.pda]]];
 
     let xfer_ctx = CpiContext::new_with_signer(
         token.to_account_info(),
-        anchor_spl::token::TransferChecked {
+        anchor_spl::token_interface::TransferChecked {
             from: ctx.accounts.pda_ata.to_account_info(),
             mint: ctx.accounts.mint_account.to_account_info(),
             to: ctx.accounts.destination_program_pda_ata.to_account_info(),
@@ -225,12 +555,17 @@ fn handle_spl_token_common(
         signer_seeds,
     );
 
-    anchor_spl::token::transfer_checked(xfer_ctx, amount, decimals)?;
+    anchor_spl::token_interface::transfer_checked(xfer_ctx, amount, decimals)?;
 
-    // 4. Invoke destination program's function
-    invoke(&ix, ctx.remaining_accounts)?;
+    // 5. Invoke destination program's function
+    let destination_program = ctx.accounts.destination_program.key();
+    let caller_seeds: &[&[&[u8]]] =
+        &[&[b"caller", destination_program.as_ref(), &[ctx.bumps.caller]]];
+    invoke_signed(&ix, ctx.remaining_accounts, caller_seeds)?;
+    let (return_data, truncated) =
+        load_return_data(get_return_data(), &ctx.accounts.destination_program.key());
 
-    // 5. Log success
+    // 6. Log success
     msg!(
         "Execute SPL done: amount = {}, decimals = {}, recipient = {}, mint = {}, pda = {}",
         amount,
@@ -240,6 +575,26 @@ fn handle_spl_token_common(
         ctx.accounts.pda.key()
     );
 
+    emit_cpi!(Executed {
+        version: EVENT_SCHEMA_VERSION,
+        nonce,
+        destination_program: ctx.accounts.destination_program.key(),
+        sender,
+        amount,
+        mint: Some(ctx.accounts.mint_account.key()),
+        data,
+        revert,
+    });
+
+    emit_cpi!(ExecuteCompleted {
+        version: EVENT_SCHEMA_VERSION,
+        nonce,
+        destination_program: ctx.accounts.destination_program.key(),
+        revert,
+        return_data,
+        truncated,
+    });
+
     Ok(())
 }
 
@@ -254,6 +609,7 @@ pub fn handle_spl_token(
     recovery_id: u8,
     message_hash: [u8; 32],
     nonce: u64,
+    instruction_id: InstructionId,
 ) -> Result<()> {
     let instruction_data = CallableInstruction::OnCall {
         amount,
@@ -272,7 +628,7 @@ pub fn handle_spl_token(
         message_hash,
         nonce,
         sender.to_vec(),
-        InstructionId::ExecuteSplToken,
+        instruction_id,
         instruction_data,
     )
 }
@@ -288,6 +644,7 @@ pub fn handle_spl_token_revert(
     recovery_id: u8,
     message_hash: [u8; 32],
     nonce: u64,
+    instruction_id: InstructionId,
 ) -> Result<()> {
     let instruction_data = CallableInstruction::OnRevert {
         amount,
@@ -306,7 +663,7 @@ pub fn handle_spl_token_revert(
         message_hash,
         nonce,
         sender.to_bytes().to_vec(),
-        InstructionId::ExecuteSplTokenRevert,
+        instruction_id,
         instruction_data,
     )
 }
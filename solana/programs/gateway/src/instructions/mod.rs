@@ -1,4 +1,5 @@
 pub mod admin;
 pub mod deposit;
 pub mod execute;
+pub mod multisig;
 pub mod withdraw;
@@ -0,0 +1,256 @@
+use crate::{
+    contexts::{
+        ApproveAdminAction, CancelAdminAction, ExecuteAdminAction, InitializeAdminConfig,
+        ProposeAdminAction,
+    },
+    errors::Errors,
+    events::{
+        AdminActionApproved, AdminActionCancelled, AdminActionExecuted, AdminActionProposed,
+        AdminConfigUpdated, AuthorityUpdated, NonceReset, TssUpdateScheduled, EVENT_SCHEMA_VERSION,
+    },
+    state::{AdminAction, AdminConfig, AdminProposal},
+    utils::{
+        schedule_tss_update, verify_authority, ADMIN_PROPOSAL_EXPIRY_SLOTS, MAX_ADMIN_SIGNERS,
+    },
+};
+use anchor_lang::prelude::*;
+
+// Initializes the admin config. Caller is authority stored in PDA.
+// Once initialized, TSS, nonce and authority updates require admin proposals.
+pub fn initialize_admin_config(
+    ctx: Context<InitializeAdminConfig>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    verify_authority(&ctx.accounts.signer.key(), &ctx.accounts.pda)?;
+    validate_admin_signers(&signers, threshold)?;
+
+    let admin_config = &mut ctx.accounts.admin_config;
+    admin_config.signers = signers.clone();
+    admin_config.threshold = threshold;
+    admin_config.proposal_count = 0;
+    admin_config.signers_epoch = 0;
+
+    msg!(
+        "Admin config initialized: signers = {:?}, threshold = {}",
+        signers,
+        threshold
+    );
+
+    emit_cpi!(AdminConfigUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        signers,
+        threshold,
+    });
+
+    Ok(())
+}
+
+// Proposes an admin action. Caller is an admin signer, whose approval is counted.
+pub fn propose_admin_action(ctx: Context<ProposeAdminAction>, action: AdminAction) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    verify_admin_signer(&signer, &ctx.accounts.admin_config)?;
+    if let AdminAction::UpdateSigners { signers, threshold } = &action {
+        validate_admin_signers(signers, *threshold)?;
+    }
+
+    let admin_config = &mut ctx.accounts.admin_config;
+    let proposal_id = admin_config.proposal_count;
+    admin_config.proposal_count += 1;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = proposal_id;
+    proposal.proposer = signer;
+    proposal.action = action.clone();
+    proposal.approvals = vec![signer];
+    proposal.signers_epoch = admin_config.signers_epoch;
+    proposal.expiry_slot = Clock::get()?.slot + ADMIN_PROPOSAL_EXPIRY_SLOTS;
+
+    msg!(
+        "Admin action proposed: id = {}, proposer = {}, action = {:?}",
+        proposal_id,
+        signer,
+        action
+    );
+
+    emit_cpi!(AdminActionProposed {
+        version: EVENT_SCHEMA_VERSION,
+        proposal_id,
+        proposer: signer,
+        action,
+    });
+
+    Ok(())
+}
+
+// Approves an admin action. Caller is an admin signer who has not approved it yet.
+pub fn approve_admin_action(ctx: Context<ApproveAdminAction>, proposal_id: u64) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    verify_admin_signer(&signer, &ctx.accounts.admin_config)?;
+    verify_proposal_active(&ctx.accounts.proposal, &ctx.accounts.admin_config)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        !proposal.approvals.contains(&signer),
+        Errors::ProposalAlreadyApproved
+    );
+    proposal.approvals.push(signer);
+
+    msg!(
+        "Admin action approved: id = {}, approver = {}, approvals = {}",
+        proposal_id,
+        signer,
+        proposal.approvals.len()
+    );
+
+    emit_cpi!(AdminActionApproved {
+        version: EVENT_SCHEMA_VERSION,
+        proposal_id,
+        approver: signer,
+        approvals: proposal.approvals.len() as u8,
+    });
+
+    Ok(())
+}
+
+// Executes an admin action approved by at least threshold admin signers. Caller is an admin signer.
+// Proposals made before the signers last changed, or past their expiry slot, cannot be executed.
+pub fn execute_admin_action(ctx: Context<ExecuteAdminAction>, proposal_id: u64) -> Result<()> {
+    verify_admin_signer(&ctx.accounts.signer.key(), &ctx.accounts.admin_config)?;
+    verify_proposal_active(&ctx.accounts.proposal, &ctx.accounts.admin_config)?;
+
+    let admin_config = &mut ctx.accounts.admin_config;
+    let action = ctx.accounts.proposal.action.clone();
+    require!(
+        ctx.accounts.proposal.approvals.len() >= admin_config.threshold as usize,
+        Errors::InsufficientApprovals
+    );
+
+    let pda = &mut ctx.accounts.pda;
+    match action.clone() {
+        AdminAction::UpdateTss {
+            tss_address,
+            activation_slot,
+            overlap_slots,
+        } => {
+            schedule_tss_update(pda, tss_address, activation_slot, overlap_slots)?;
+
+            emit_cpi!(TssUpdateScheduled {
+                version: EVENT_SCHEMA_VERSION,
+                tss_address: pda.tss_address,
+                pending_tss_address: tss_address,
+                activation_slot: pda.tss_activation_slot,
+                overlap_end_slot: pda.tss_overlap_end_slot,
+                nonce: pda.nonce,
+            });
+        }
+        AdminAction::ResetNonce { nonce } => {
+            let previous_nonce = pda.nonce;
+            pda.nonce = nonce;
+
+            emit_cpi!(NonceReset {
+                version: EVENT_SCHEMA_VERSION,
+                previous_nonce,
+                nonce,
+            });
+        }
+        AdminAction::UpdateAuthority { authority } => {
+            let previous_authority = pda.authority;
+            pda.authority = authority;
+
+            emit_cpi!(AuthorityUpdated {
+                version: EVENT_SCHEMA_VERSION,
+                previous_authority,
+                authority,
+            });
+        }
+        AdminAction::UpdateSigners { signers, threshold } => {
+            admin_config.signers = signers.clone();
+            admin_config.threshold = threshold;
+            admin_config.signers_epoch += 1;
+
+            emit_cpi!(AdminConfigUpdated {
+                version: EVENT_SCHEMA_VERSION,
+                signers,
+                threshold,
+            });
+        }
+    }
+
+    msg!(
+        "Admin action executed: id = {}, action = {:?}",
+        proposal_id,
+        action
+    );
+
+    emit_cpi!(AdminActionExecuted {
+        version: EVENT_SCHEMA_VERSION,
+        proposal_id,
+        action,
+    });
+
+    Ok(())
+}
+
+// Cancels an admin action and closes the proposal. Caller is the proposer.
+// Other admin signers cannot cancel a proposal; unapproved proposals lapse at their expiry slot.
+pub fn cancel_admin_action(ctx: Context<CancelAdminAction>, proposal_id: u64) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    require_keys_eq!(
+        signer,
+        ctx.accounts.proposal.proposer,
+        Errors::SignerIsNotProposer
+    );
+
+    msg!(
+        "Admin action cancelled: id = {}, cancelled by = {}",
+        proposal_id,
+        signer
+    );
+
+    emit_cpi!(AdminActionCancelled {
+        version: EVENT_SCHEMA_VERSION,
+        proposal_id,
+        cancelled_by: signer,
+    });
+
+    Ok(())
+}
+
+// Verifies that the proposal was made by the current admin signers and has not expired.
+fn verify_proposal_active(proposal: &AdminProposal, admin_config: &AdminConfig) -> Result<()> {
+    require!(
+        proposal.signers_epoch == admin_config.signers_epoch,
+        Errors::ProposalExpired
+    );
+    require!(
+        Clock::get()?.slot <= proposal.expiry_slot,
+        Errors::ProposalExpired
+    );
+    Ok(())
+}
+
+// Verifies that the signer is one of the admin signers.
+fn verify_admin_signer(signer: &Pubkey, admin_config: &AdminConfig) -> Result<()> {
+    require!(
+        admin_config.signers.contains(signer),
+        Errors::SignerIsNotAdmin
+    );
+    Ok(())
+}
+
+// Validates a set of unique admin signers and a threshold they can reach.
+fn validate_admin_signers(signers: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_ADMIN_SIGNERS,
+        Errors::InvalidAdminConfig
+    );
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        Errors::InvalidAdminConfig
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        require!(!signers[..i].contains(signer), Errors::InvalidAdminConfig);
+    }
+    Ok(())
+}
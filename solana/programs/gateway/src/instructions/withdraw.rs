@@ -1,12 +1,16 @@
 use crate::{
-    contexts::{Withdraw, WithdrawSPLToken},
-    state::InstructionId,
-    utils::{validate_message, verify_ata_match, DEFAULT_GAS_COST},
+    contexts::{FinalizeWithdrawal, Withdraw, WithdrawSPLToken, WithdrawWsol},
+    errors::Errors,
+    events::{WithdrawalQueued, Withdrawn, EVENT_SCHEMA_VERSION},
+    state::{InstructionId, PendingWithdrawal},
+    utils::{
+        apply_rate_limit, create_pending_withdrawal, create_recipient_ata, load_whitelist_entry,
+        validate_message, verify_ata_match, verify_custody_balance, wrap_sol, DEFAULT_GAS_COST,
+    },
 };
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program::invoke;
-use anchor_spl::token::transfer_checked;
-use spl_associated_token_account::instruction::create_associated_token_account;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::transfer_checked;
 
 // Withdraws SOL. Caller is TSS.
 pub fn handle_sol(
@@ -31,11 +35,54 @@ pub fn handle_sol(
         recovery_id,
     )?;
 
-    // 2. Transfer SOL
+    // 2. Apply rate limit, queueing the withdrawal if it is above the delay threshold
+    let delay_slots = apply_rate_limit(&ctx.accounts.rate_limit, amount)?;
+    if delay_slots > 0 {
+        let release_slot = Clock::get()?.slot + delay_slots;
+        create_pending_withdrawal(
+            &ctx.accounts.pending_withdrawal,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            ctx.bumps.pending_withdrawal,
+            &PendingWithdrawal {
+                nonce,
+                message_hash,
+                mint: None,
+                rate_limit_mint: Pubkey::default(),
+                recipient: ctx.accounts.recipient.key(),
+                recipient_ata: Pubkey::default(),
+                amount,
+                decimals: 9,
+                release_slot,
+                payer: ctx.accounts.signer.key(),
+            },
+        )?;
+
+        msg!(
+            "Withdraw queued: amount = {}, recipient = {}, release slot = {}",
+            amount,
+            ctx.accounts.recipient.key(),
+            release_slot
+        );
+
+        emit_cpi!(WithdrawalQueued {
+            version: EVENT_SCHEMA_VERSION,
+            nonce,
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            mint: None,
+            release_slot,
+        });
+
+        return Ok(());
+    }
+
+    // 3. Transfer SOL
+    verify_custody_balance(pda, amount)?;
     pda.sub_lamports(amount)?;
     ctx.accounts.recipient.add_lamports(amount)?;
 
-    // 3. Log success
+    // 4. Log success
     msg!(
         "Withdraw executed: amount = {}, recipient = {}, pda = {}",
         amount,
@@ -43,6 +90,14 @@ pub fn handle_sol(
         ctx.accounts.pda.key()
     );
 
+    emit_cpi!(Withdrawn {
+        version: EVENT_SCHEMA_VERSION,
+        nonce,
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        mint: None,
+    });
+
     Ok(())
 }
 
@@ -73,97 +128,337 @@ pub fn handle_spl(
         recovery_id,
     )?;
 
-    // 2. Verify token accounts
+    // 2. Apply rate limit
+    let delay_slots = apply_rate_limit(&ctx.accounts.rate_limit, amount)?;
+
+    // 3. Verify the mint settings, if the whitelist entry holds them, and token accounts
+    if let Some(whitelist_entry) = load_whitelist_entry(&ctx.accounts.whitelist_entry)? {
+        require!(
+            decimals == whitelist_entry.decimals,
+            Errors::DecimalsMismatch
+        );
+        require!(
+            ctx.accounts.token_program.key() == whitelist_entry.token_program,
+            Errors::InvalidTokenProgram
+        );
+    }
+
     verify_ata_match(
         &pda.key(),
         &ctx.accounts.mint_account.key(),
+        &ctx.accounts.token_program.key(),
         &ctx.accounts.pda_ata.key(),
     )?;
 
     verify_ata_match(
         &ctx.accounts.recipient.key(),
         &ctx.accounts.mint_account.key(),
+        &ctx.accounts.token_program.key(),
         &ctx.accounts.recipient_ata.key(),
     )?;
 
-    // 3. Create recipient ATA if needed and calculate costs
-    let mut cost_ata_create: u64 = 0;
-    let recipient_ata_account = ctx.accounts.recipient_ata.to_account_info();
+    // 4. Create recipient ATA if needed and calculate costs
+    let cost_ata_create = create_recipient_ata(
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.recipient.to_account_info(),
+        &ctx.accounts.recipient_ata,
+        &ctx.accounts.mint_account.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.associated_token_program.to_account_info(),
+    )?;
 
-    if recipient_ata_account.lamports() == 0
-        || *recipient_ata_account.owner == ctx.accounts.system_program.key()
-    {
-        // ATA needs to be created
-        msg!(
-            "Creating associated token account {:?} for recipient {:?}...",
-            recipient_ata_account.key(),
-            ctx.accounts.recipient.key(),
-        );
+    // 5. Reimburse gas costs
+    let reimbursement = DEFAULT_GAS_COST + cost_ata_create;
+    verify_custody_balance(pda, reimbursement)?;
+    pda.sub_lamports(reimbursement)?;
+    ctx.accounts.signer.add_lamports(reimbursement)?;
 
-        let bal_before = ctx.accounts.signer.lamports();
-        invoke(
-            &create_associated_token_account(
-                ctx.accounts.signer.to_account_info().key,
-                ctx.accounts.recipient.to_account_info().key,
-                ctx.accounts.mint_account.to_account_info().key,
-                ctx.accounts.token_program.key,
-            ),
-            &[
-                ctx.accounts.mint_account.to_account_info().clone(),
-                ctx.accounts.recipient_ata.clone(),
-                ctx.accounts.recipient.to_account_info().clone(),
-                ctx.accounts.signer.to_account_info().clone(),
-                ctx.accounts.system_program.to_account_info().clone(),
-                ctx.accounts.token_program.to_account_info().clone(),
-                ctx.accounts
-                    .associated_token_program
-                    .to_account_info()
-                    .clone(),
-            ],
+    // 6. Queue the withdrawal if it is above the delay threshold
+    if delay_slots > 0 {
+        let release_slot = Clock::get()?.slot + delay_slots;
+        create_pending_withdrawal(
+            &ctx.accounts.pending_withdrawal,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            ctx.bumps.pending_withdrawal,
+            &PendingWithdrawal {
+                nonce,
+                message_hash,
+                mint: Some(ctx.accounts.mint_account.key()),
+                rate_limit_mint: ctx.accounts.mint_account.key(),
+                recipient: ctx.accounts.recipient.key(),
+                recipient_ata: ctx.accounts.recipient_ata.key(),
+                amount,
+                decimals,
+                release_slot,
+                payer: ctx.accounts.signer.key(),
+            },
         )?;
 
-        let bal_after = ctx.accounts.signer.lamports();
-        cost_ata_create = bal_before - bal_after;
-
-        msg!("Associated token account created!");
         msg!(
-            "Refunding the rent ({:?} lamports) paid by the signer {:?}",
-            cost_ata_create,
-            ctx.accounts.signer.to_account_info().key
+            "Withdraw SPL queued: amount = {}, recipient = {}, mint = {}, release slot = {}",
+            amount,
+            ctx.accounts.recipient.key(),
+            ctx.accounts.mint_account.key(),
+            release_slot
         );
+
+        emit_cpi!(WithdrawalQueued {
+            version: EVENT_SCHEMA_VERSION,
+            nonce,
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            mint: Some(ctx.accounts.mint_account.key()),
+            release_slot,
+        });
+
+        return Ok(());
     }
 
-    // 4. Transfer tokens
+    // 7. Transfer tokens
     let token = &ctx.accounts.token_program;
     let signer_seeds: &[&[&[u8]]] = &[&[b"meta", &[ctx.bumps.pda]]];
 
     let xfer_ctx = CpiContext::new_with_signer(
         token.to_account_info(),
-        anchor_spl::token::TransferChecked {
+        anchor_spl::token_interface::TransferChecked {
             from: ctx.accounts.pda_ata.to_account_info(),
             mint: ctx.accounts.mint_account.to_account_info(),
             to: ctx.accounts.recipient_ata.to_account_info(),
-            authority: pda.to_account_info(),
+            authority: ctx.accounts.pda.to_account_info(),
         },
         signer_seeds,
     );
 
     transfer_checked(xfer_ctx, amount, decimals)?;
 
-    // 5. Reimburse gas costs
+    // 8. Log success
+    msg!(
+        "Withdraw SPL executed: amount = {}, decimals = {}, recipient = {}, mint = {}, pda = {}",
+        amount,
+        decimals,
+        ctx.accounts.recipient.key(),
+        ctx.accounts.mint_account.key(),
+        ctx.accounts.pda.key()
+    );
+
+    emit_cpi!(Withdrawn {
+        version: EVENT_SCHEMA_VERSION,
+        nonce,
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        mint: Some(ctx.accounts.mint_account.key()),
+    });
+
+    Ok(())
+}
+
+// Withdraws SOL delivered as wrapped SOL to the recipient's associated token account. Caller is TSS.
+pub fn handle_wsol(
+    ctx: Context<WithdrawWsol>,
+    amount: u64,
+    signature: [u8; 64],
+    recovery_id: u8,
+    message_hash: [u8; 32],
+    nonce: u64,
+) -> Result<()> {
+    let pda = &mut ctx.accounts.pda;
+
+    // 1. Verify cross-chain message
+    validate_message(
+        pda,
+        InstructionId::WithdrawWsol,
+        nonce,
+        amount,
+        &[&ctx.accounts.recipient.key().to_bytes()],
+        &message_hash,
+        &signature,
+        recovery_id,
+    )?;
+
+    // 2. Apply the SOL rate limit
+    let delay_slots = apply_rate_limit(&ctx.accounts.rate_limit, amount)?;
+
+    // 3. Create recipient wrapped SOL ATA if needed and reimburse costs
+    verify_ata_match(
+        &ctx.accounts.recipient.key(),
+        &native_mint::ID,
+        &ctx.accounts.token_program.key(),
+        &ctx.accounts.recipient_ata.key(),
+    )?;
+
+    let cost_ata_create = create_recipient_ata(
+        &ctx.accounts.signer.to_account_info(),
+        &ctx.accounts.recipient.to_account_info(),
+        &ctx.accounts.recipient_ata,
+        &ctx.accounts.native_mint.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.associated_token_program.to_account_info(),
+    )?;
+
     let reimbursement = DEFAULT_GAS_COST + cost_ata_create;
+    verify_custody_balance(pda, reimbursement)?;
     pda.sub_lamports(reimbursement)?;
     ctx.accounts.signer.add_lamports(reimbursement)?;
 
+    // 4. Queue the withdrawal if it is above the delay threshold
+    if delay_slots > 0 {
+        let release_slot = Clock::get()?.slot + delay_slots;
+        create_pending_withdrawal(
+            &ctx.accounts.pending_withdrawal,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            ctx.bumps.pending_withdrawal,
+            &PendingWithdrawal {
+                nonce,
+                message_hash,
+                mint: Some(native_mint::ID),
+                rate_limit_mint: Pubkey::default(),
+                recipient: ctx.accounts.recipient.key(),
+                recipient_ata: ctx.accounts.recipient_ata.key(),
+                amount,
+                decimals: native_mint::DECIMALS,
+                release_slot,
+                payer: ctx.accounts.signer.key(),
+            },
+        )?;
+
+        msg!(
+            "Withdraw wSOL queued: amount = {}, recipient = {}, release slot = {}",
+            amount,
+            ctx.accounts.recipient.key(),
+            release_slot
+        );
+
+        emit_cpi!(WithdrawalQueued {
+            version: EVENT_SCHEMA_VERSION,
+            nonce,
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            mint: Some(native_mint::ID),
+            release_slot,
+        });
+
+        return Ok(());
+    }
+
+    // 5. Wrap SOL into the recipient ATA
+    verify_custody_balance(&ctx.accounts.pda, amount)?;
+    wrap_sol(
+        &ctx.accounts.pda.to_account_info(),
+        &ctx.accounts.recipient_ata,
+        &ctx.accounts.token_program.to_account_info(),
+        amount,
+    )?;
+
     // 6. Log success
     msg!(
-        "Withdraw SPL executed: amount = {}, decimals = {}, recipient = {}, mint = {}, pda = {}",
+        "Withdraw wSOL executed: amount = {}, recipient = {}, pda = {}",
         amount,
-        decimals,
         ctx.accounts.recipient.key(),
-        ctx.accounts.mint_account.key(),
         ctx.accounts.pda.key()
     );
 
+    emit_cpi!(Withdrawn {
+        version: EVENT_SCHEMA_VERSION,
+        nonce,
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        mint: Some(native_mint::ID),
+    });
+
+    Ok(())
+}
+
+// Releases a queued withdrawal once its release slot is reached. Callable by anyone.
+pub fn handle_finalize(ctx: Context<FinalizeWithdrawal>, _message_hash: [u8; 32]) -> Result<()> {
+    let pending = &ctx.accounts.pending_withdrawal;
+    require!(
+        Clock::get()?.slot >= pending.release_slot,
+        Errors::WithdrawalNotReleasable
+    );
+
+    // 1. Release funds
+    match pending.mint {
+        None => {
+            verify_custody_balance(&ctx.accounts.pda, pending.amount)?;
+            ctx.accounts.pda.sub_lamports(pending.amount)?;
+            ctx.accounts.recipient.add_lamports(pending.amount)?;
+        }
+        Some(mint) if mint == native_mint::ID => {
+            let (Some(recipient_ata), Some(token_program)) =
+                (&ctx.accounts.recipient_ata, &ctx.accounts.token_program)
+            else {
+                return err!(Errors::InvalidPendingWithdrawal);
+            };
+            require_keys_eq!(
+                recipient_ata.key(),
+                pending.recipient_ata,
+                Errors::InvalidPendingWithdrawal
+            );
+
+            verify_custody_balance(&ctx.accounts.pda, pending.amount)?;
+            wrap_sol(
+                &ctx.accounts.pda.to_account_info(),
+                &recipient_ata.to_account_info(),
+                &token_program.to_account_info(),
+                pending.amount,
+            )?;
+        }
+        Some(mint) => {
+            let (Some(pda_ata), Some(mint_account), Some(recipient_ata), Some(token_program)) = (
+                &ctx.accounts.pda_ata,
+                &ctx.accounts.mint_account,
+                &ctx.accounts.recipient_ata,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(Errors::InvalidPendingWithdrawal);
+            };
+            require_keys_eq!(mint_account.key(), mint, Errors::InvalidPendingWithdrawal);
+            require_keys_eq!(
+                recipient_ata.key(),
+                pending.recipient_ata,
+                Errors::InvalidPendingWithdrawal
+            );
+            verify_ata_match(
+                &ctx.accounts.pda.key(),
+                &mint,
+                &token_program.key(),
+                &pda_ata.key(),
+            )?;
+
+            let signer_seeds: &[&[&[u8]]] = &[&[b"meta", &[ctx.bumps.pda]]];
+            let xfer_ctx = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                anchor_spl::token_interface::TransferChecked {
+                    from: pda_ata.to_account_info(),
+                    mint: mint_account.to_account_info(),
+                    to: recipient_ata.to_account_info(),
+                    authority: ctx.accounts.pda.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer_checked(xfer_ctx, pending.amount, pending.decimals)?;
+        }
+    }
+
+    // 2. Log success
+    msg!(
+        "Queued withdraw finalized: nonce = {}, amount = {}, recipient = {}",
+        pending.nonce,
+        pending.amount,
+        pending.recipient
+    );
+
+    emit_cpi!(Withdrawn {
+        version: EVENT_SCHEMA_VERSION,
+        nonce: pending.nonce,
+        recipient: pending.recipient,
+        amount: pending.amount,
+        mint: pending.mint,
+    });
+
     Ok(())
 }
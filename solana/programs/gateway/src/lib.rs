@@ -2,12 +2,14 @@ use anchor_lang::prelude::*;
 
 mod contexts;
 mod errors;
+mod events;
 mod instructions;
 mod state;
 mod utils;
 
 pub use contexts::*;
 pub use errors::*;
+pub use events::*;
 pub use state::*;
 pub use utils::DEPOSIT_FEE;
 
@@ -34,6 +36,23 @@ pub mod gateway {
         instructions::admin::initialize(ctx, tss_address, chain_id)
     }
 
+    /// Migrates the gateway PDA from the unversioned layout to the current layout.
+    /// Caller is authority stored in PDA, who tops up rent for the larger account if needed.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    pub fn upgrade_pda(ctx: Context<UpgradePda>) -> Result<()> {
+        instructions::admin::upgrade_pda(ctx)
+    }
+
+    /// Migrates a whitelist entry from the unversioned, empty layout to the current layout.
+    /// Entries must be migrated before the mint can be deposited, unwhitelisted or re-whitelisted.
+    /// Caller is authority stored in PDA, who tops up rent for the larger account if needed.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    pub fn upgrade_whitelist_entry(ctx: Context<UpgradeWhitelistEntry>) -> Result<()> {
+        instructions::admin::upgrade_whitelist_entry(ctx)
+    }
+
     /// Increments nonce, used by TSS in case outbound fails.
     /// # Arguments
     /// * `ctx` - The instruction context.
@@ -63,6 +82,40 @@ pub mod gateway {
         )
     }
 
+    /// Increments nonce with a typed failure code, used by TSS in case outbound fails.
+    /// The amount is refunded to the revert or abort address of the original message
+    /// if the refund accounts are passed.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount` - The amount in original outbound.
+    /// * `signature` - The TSS signature.
+    /// * `recovery_id` - The recovery ID for signature verification.
+    /// * `message_hash` - Message hash for signature verification.
+    /// * `nonce` - The current nonce value.
+    /// * `failure_code` - The failure code of original outbound.
+    /// * `original_instruction_id` - The instruction ID of original outbound, committed to by its numeric value.
+    pub fn increment_nonce_v2(
+        ctx: Context<IncrementNonceV2>,
+        amount: u64,
+        signature: [u8; 64],
+        recovery_id: u8,
+        message_hash: [u8; 32],
+        nonce: u64,
+        failure_code: FailureCode,
+        original_instruction_id: InstructionId,
+    ) -> Result<()> {
+        instructions::execute::increment_nonce_v2(
+            ctx,
+            amount,
+            signature,
+            recovery_id,
+            message_hash,
+            nonce,
+            failure_code,
+            original_instruction_id,
+        )
+    }
+
     /// Withdraws amount to destination program pda, and calls on_call on destination program
     /// # Arguments
     /// * `ctx` - The instruction context.
@@ -92,6 +145,7 @@ pub mod gateway {
             recovery_id,
             message_hash,
             nonce,
+            InstructionId::ExecuteSol,
         )
     }
 
@@ -125,6 +179,7 @@ pub mod gateway {
             recovery_id,
             message_hash,
             nonce,
+            InstructionId::ExecuteSolRevert,
         )
     }
 
@@ -160,6 +215,7 @@ pub mod gateway {
             recovery_id,
             message_hash,
             nonce,
+            InstructionId::ExecuteSplToken,
         )
     }
 
@@ -196,6 +252,155 @@ pub mod gateway {
             recovery_id,
             message_hash,
             nonce,
+            InstructionId::ExecuteSplTokenRevert,
+        )
+    }
+
+    /// Withdraws amount to destination program pda, and calls on_call on destination program
+    /// The message hash also commits to the ordered forwarded accounts and their writability,
+    /// as passed in the remaining accounts before the forwarded account policy is applied.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount` - Amount of SOL to transfer.
+    /// * `sender` - Sender's address.
+    /// * `data` - Arbitrary data to pass to the destination program.
+    /// * `signature` - Signature of the message.
+    /// * `recovery_id` - Recovery ID of the signature.
+    /// * `message_hash` - Hash of the message.
+    /// * `nonce` - Nonce of the message.
+    pub fn execute_v2(
+        ctx: Context<Execute>,
+        amount: u64,
+        sender: [u8; 20],
+        data: Vec<u8>,
+        signature: [u8; 64],
+        recovery_id: u8,
+        message_hash: [u8; 32],
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::execute::handle_sol(
+            ctx,
+            amount,
+            sender,
+            data,
+            signature,
+            recovery_id,
+            message_hash,
+            nonce,
+            InstructionId::ExecuteSolV2,
+        )
+    }
+
+    /// Withdraws amount to destination program pda, and calls on_revert on destination program
+    /// The message hash also commits to the ordered forwarded accounts and their writability,
+    /// as passed in the remaining accounts before the forwarded account policy is applied.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount` - The amount of SOL to withdraw.
+    /// * `sender` - Sender from ZEVM.
+    /// * `data` - Data to pass to destination program.
+    /// * `signature` - The TSS signature.
+    /// * `recovery_id` - The recovery ID for signature verification.
+    /// * `message_hash` - Message hash for signature verification.
+    /// * `nonce` - The current nonce value.
+    pub fn execute_revert_v2(
+        ctx: Context<Execute>,
+        amount: u64,
+        sender: Pubkey,
+        data: Vec<u8>,
+        signature: [u8; 64],
+        recovery_id: u8,
+        message_hash: [u8; 32],
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::execute::handle_sol_revert(
+            ctx,
+            amount,
+            sender,
+            data,
+            signature,
+            recovery_id,
+            message_hash,
+            nonce,
+            InstructionId::ExecuteSolRevertV2,
+        )
+    }
+
+    /// Withdraws amount of SPL tokens to destination program pda, and calls on_call on destination program
+    /// The message hash also commits to the ordered forwarded accounts and their writability,
+    /// as passed in the remaining accounts before the forwarded account policy is applied.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `decimals` - Token decimals for precision.
+    /// * `amount` - The amount of tokens to withdraw.
+    /// * `sender` - Sender from ZEVM.
+    /// * `data` - Data to pass to destination program.
+    /// * `signature` - The TSS signature.
+    /// * `recovery_id` - The recovery ID for signature verification.
+    /// * `message_hash` - Message hash for signature verification.
+    /// * `nonce` - The current nonce value.
+    pub fn execute_spl_token_v2(
+        ctx: Context<ExecuteSPLToken>,
+        decimals: u8,
+        amount: u64,
+        sender: [u8; 20],
+        data: Vec<u8>,
+        signature: [u8; 64],
+        recovery_id: u8,
+        message_hash: [u8; 32],
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::execute::handle_spl_token(
+            ctx,
+            decimals,
+            amount,
+            sender,
+            data,
+            signature,
+            recovery_id,
+            message_hash,
+            nonce,
+            InstructionId::ExecuteSplTokenV2,
+        )
+    }
+
+    /// Withdraws SPL token amount to destination program pda, and calls on_revert on destination program
+    /// The message hash also commits to the ordered forwarded accounts and their writability,
+    /// as passed in the remaining accounts before the forwarded account policy is applied.
+    ///
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `decimals` - Token decimals for precision.
+    /// * `amount` - The amount of tokens to withdraw.
+    /// * `sender` - Sender from ZEVM.
+    /// * `data` - Data to pass to destination program.
+    /// * `signature` - The TSS signature.
+    /// * `recovery_id` - The recovery ID for signature verification.
+    /// * `message_hash` - Message hash for signature verification.
+    /// * `nonce` - The current nonce value.
+    pub fn execute_spl_token_revert_v2(
+        ctx: Context<ExecuteSPLToken>,
+        decimals: u8,
+        amount: u64,
+        sender: Pubkey,
+        data: Vec<u8>,
+        signature: [u8; 64],
+        recovery_id: u8,
+        message_hash: [u8; 32],
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::execute::handle_spl_token_revert(
+            ctx,
+            decimals,
+            amount,
+            sender,
+            data,
+            signature,
+            recovery_id,
+            message_hash,
+            nonce,
+            InstructionId::ExecuteSplTokenRevertV2,
         )
     }
 
@@ -207,12 +412,21 @@ pub mod gateway {
         instructions::admin::set_deposit_paused(ctx, deposit_paused)
     }
 
-    /// Updates the TSS address. Caller is authority stored in PDA.
+    /// Schedules a TSS rotation. Caller is authority stored in PDA.
+    /// Signatures from both TSS addresses are accepted from `activation_slot` for `overlap_slots`,
+    /// after which the new TSS replaces the current one. The nonce carries over unchanged.
     /// # Arguments
     /// * `ctx` - The instruction context.
     /// * `tss_address` - The new Ethereum TSS address (20 bytes).
-    pub fn update_tss(ctx: Context<UpdateTss>, tss_address: [u8; 20]) -> Result<()> {
-        instructions::admin::update_tss(ctx, tss_address)
+    /// * `activation_slot` - The slot from which the new TSS address is accepted.
+    /// * `overlap_slots` - The number of slots during which both TSS addresses are accepted.
+    pub fn update_tss(
+        ctx: Context<UpdateTss>,
+        tss_address: [u8; 20],
+        activation_slot: u64,
+        overlap_slots: u64,
+    ) -> Result<()> {
+        instructions::admin::update_tss(ctx, tss_address, activation_slot, overlap_slots)
     }
 
     /// Updates the PDA authority. Caller is authority stored in PDA.
@@ -235,6 +449,214 @@ pub mod gateway {
         instructions::admin::reset_nonce(ctx, new_nonce)
     }
 
+    /// Initializes the M-of-N admin config. Caller is authority stored in PDA.
+    /// Once initialized, `update_tss`, `reset_nonce` and `update_authority` go through admin proposals.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `signers` - The admin signers.
+    /// * `threshold` - The number of approvals required to execute an admin action.
+    pub fn initialize_admin_config(
+        ctx: Context<InitializeAdminConfig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::multisig::initialize_admin_config(ctx, signers, threshold)
+    }
+
+    /// Proposes an admin action. Caller is an admin signer, whose approval is counted.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `action` - The proposed admin action.
+    pub fn propose_admin_action(
+        ctx: Context<ProposeAdminAction>,
+        action: AdminAction,
+    ) -> Result<()> {
+        instructions::multisig::propose_admin_action(ctx, action)
+    }
+
+    /// Approves an admin action. Caller is an admin signer.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `proposal_id` - The ID of the proposal to approve.
+    pub fn approve_admin_action(ctx: Context<ApproveAdminAction>, proposal_id: u64) -> Result<()> {
+        instructions::multisig::approve_admin_action(ctx, proposal_id)
+    }
+
+    /// Executes an admin action approved by at least threshold admin signers. Caller is an admin signer.
+    /// Proposals expire after `ADMIN_PROPOSAL_EXPIRY_SLOTS` or when the admin signers change.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `proposal_id` - The ID of the proposal to execute.
+    pub fn execute_admin_action(ctx: Context<ExecuteAdminAction>, proposal_id: u64) -> Result<()> {
+        instructions::multisig::execute_admin_action(ctx, proposal_id)
+    }
+
+    /// Cancels an admin action and closes the proposal. Caller is the proposer.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `proposal_id` - The ID of the proposal to cancel.
+    pub fn cancel_admin_action(ctx: Context<CancelAdminAction>, proposal_id: u64) -> Result<()> {
+        instructions::multisig::cancel_admin_action(ctx, proposal_id)
+    }
+
+    /// Updates the deposit fees. Caller is TSS or authority stored in PDA.
+    /// If signature is zero, authority must sign the transaction.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `deposit_fee` - The fee in lamports charged on SOL deposits, at most `MAX_DEPOSIT_FEE`.
+    /// * `spl_deposit_fee` - The fee in lamports charged on SPL token deposits, at most `MAX_DEPOSIT_FEE`.
+    /// * `signature` - The TSS signature.
+    /// * `recovery_id` - The recovery ID for signature verification.
+    /// * `message_hash` - Message hash for signature verification.
+    /// * `nonce` - The current nonce value.
+    pub fn update_deposit_fee(
+        ctx: Context<UpdateDepositFee>,
+        deposit_fee: u64,
+        spl_deposit_fee: u64,
+        signature: [u8; 64],
+        recovery_id: u8,
+        message_hash: [u8; 32],
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::admin::update_deposit_fee(
+            ctx,
+            deposit_fee,
+            spl_deposit_fee,
+            signature,
+            recovery_id,
+            message_hash,
+            nonce,
+        )
+    }
+
+    /// Updates the minimum SOL deposit. Caller is authority stored in PDA.
+    /// Minimum SPL token deposits are set per mint with `update_whitelist_entry`.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `min_deposit` - The minimum amount in lamports accepted per SOL deposit.
+    pub fn update_min_deposit(ctx: Context<UpdateMinDeposit>, min_deposit: u64) -> Result<()> {
+        instructions::admin::update_min_deposit(ctx, min_deposit)
+    }
+
+    /// Updates the swap program used by `deposit_with_swap` and `deposit_sol_with_swap`. Caller is authority stored in PDA.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `swap_program` - The swap program, default to disable swaps.
+    pub fn update_swap_program(
+        ctx: Context<UpdateSwapProgram>,
+        swap_program: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::update_swap_program(ctx, swap_program)
+    }
+
+    /// Updates the treasury receiving withdrawn deposit fees. Caller is authority stored in PDA.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `treasury` - The new treasury address.
+    pub fn update_treasury(ctx: Context<UpdateTreasury>, treasury: Pubkey) -> Result<()> {
+        instructions::admin::update_treasury(ctx, treasury)
+    }
+
+    /// Withdraws collected deposit fees to the treasury. Caller is authority stored in PDA.
+    /// The PDA always keeps its rent-exempt minimum, and SOL withdrawals cannot spend collected fees.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount` - The amount of lamports to withdraw.
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        instructions::admin::withdraw_fees(ctx, amount)
+    }
+
+    /// Cancels a queued withdrawal, keeping the funds in the gateway. Caller is authority stored in PDA.
+    /// The cancelled amount is credited back to the rate limit window of the asset,
+    /// so the rate limit must exist and be set again first if it was removed.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `message_hash` - The message hash of the queued withdrawal.
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>, message_hash: [u8; 32]) -> Result<()> {
+        instructions::admin::cancel_withdrawal(ctx, message_hash)
+    }
+
+    /// Sets an outbound rate limit applied to withdrawals and executions. Caller is authority stored in PDA.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `mint` - The rate limited mint, `Pubkey::default()` for SOL.
+    /// * `max_per_withdrawal` - Maximum amount released by a single outbound.
+    /// * `max_per_window` - Maximum amount released within the rolling window.
+    /// * `window_slots` - Length of the rolling window in slots.
+    /// * `delay_threshold` - Withdrawals above this amount are queued.
+    /// * `delay_slots` - Number of slots a queued withdrawal waits, zero disables queueing.
+    pub fn set_rate_limit(
+        ctx: Context<SetRateLimit>,
+        mint: Pubkey,
+        max_per_withdrawal: u64,
+        max_per_window: u64,
+        window_slots: u64,
+        delay_threshold: u64,
+        delay_slots: u64,
+    ) -> Result<()> {
+        instructions::admin::set_rate_limit(
+            ctx,
+            mint,
+            max_per_withdrawal,
+            max_per_window,
+            window_slots,
+            delay_threshold,
+            delay_slots,
+        )
+    }
+
+    /// Updates an outbound rate limit. Caller is authority stored in PDA.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `mint` - The rate limited mint, `Pubkey::default()` for SOL.
+    /// * `max_per_withdrawal` - Maximum amount released by a single outbound.
+    /// * `max_per_window` - Maximum amount released within the rolling window.
+    /// * `window_slots` - Length of the rolling window in slots.
+    /// * `delay_threshold` - Withdrawals above this amount are queued.
+    /// * `delay_slots` - Number of slots a queued withdrawal waits, zero disables queueing.
+    pub fn update_rate_limit(
+        ctx: Context<UpdateRateLimit>,
+        mint: Pubkey,
+        max_per_withdrawal: u64,
+        max_per_window: u64,
+        window_slots: u64,
+        delay_threshold: u64,
+        delay_slots: u64,
+    ) -> Result<()> {
+        instructions::admin::update_rate_limit(
+            ctx,
+            mint,
+            max_per_withdrawal,
+            max_per_window,
+            window_slots,
+            delay_threshold,
+            delay_slots,
+        )
+    }
+
+    /// Removes an outbound rate limit. Caller is authority stored in PDA.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `mint` - The rate limited mint, `Pubkey::default()` for SOL.
+    pub fn remove_rate_limit(ctx: Context<RemoveRateLimit>, mint: Pubkey) -> Result<()> {
+        instructions::admin::remove_rate_limit(ctx, mint)
+    }
+
+    /// Exempts a program depositing through CPI from deposit fees. Caller is authority stored in PDA.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `program_id` - The program to exempt.
+    pub fn add_fee_exemption(ctx: Context<AddFeeExemption>, program_id: Pubkey) -> Result<()> {
+        instructions::admin::add_fee_exemption(ctx, program_id)
+    }
+
+    /// Removes a program's deposit fee exemption. Caller is authority stored in PDA.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    pub fn remove_fee_exemption(ctx: Context<RemoveFeeExemption>) -> Result<()> {
+        instructions::admin::remove_fee_exemption(ctx)
+    }
+
     /// Whitelists a new SPL token. Caller is TSS.
     /// # Arguments
     /// * `ctx` - The instruction context.
@@ -252,7 +674,7 @@ pub mod gateway {
         instructions::admin::whitelist_spl_mint(ctx, signature, recovery_id, message_hash, nonce)
     }
 
-    /// Unwhitelists an SPL token. Caller is TSS.
+    /// Unwhitelists an SPL token, keeping it withdraw-only so TSS withdrawals still work. Caller is TSS.
     /// # Arguments
     /// * `ctx` - The instruction context.
     /// * `signature` - The TSS signature.
@@ -269,6 +691,37 @@ pub mod gateway {
         instructions::admin::unwhitelist_spl_mint(ctx, signature, recovery_id, message_hash, nonce)
     }
 
+    /// Updates the per-mint deposit settings of a whitelisted SPL token. Caller is authority stored in PDA.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `min_deposit` - The minimum amount accepted per deposit.
+    /// * `max_deposit` - The maximum amount accepted per deposit.
+    /// * `deposit_paused` - Whether deposits of the mint are paused.
+    /// * `deposit_fee` - The deposit fee in lamports, at most `MAX_DEPOSIT_FEE`, `None` to use the gateway SPL deposit fee.
+    pub fn update_whitelist_entry(
+        ctx: Context<UpdateWhitelistEntry>,
+        min_deposit: u64,
+        max_deposit: u64,
+        deposit_paused: bool,
+        deposit_fee: Option<u64>,
+    ) -> Result<()> {
+        instructions::admin::update_whitelist_entry(
+            ctx,
+            min_deposit,
+            max_deposit,
+            deposit_paused,
+            deposit_fee,
+        )
+    }
+
+    /// Sweeps the gateway ATA of an unwhitelisted mint to the treasury ATA and closes it.
+    /// Caller is authority stored in PDA, who receives the rent.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    pub fn close_mint_vault(ctx: Context<CloseMintVault>) -> Result<()> {
+        instructions::admin::close_mint_vault(ctx)
+    }
+
     /// Deposits SOL into the program and credits the `receiver` on ZetaChain zEVM.
     /// # Arguments
     /// * `ctx` - The instruction context.
@@ -281,7 +734,7 @@ pub mod gateway {
         receiver: [u8; 20],
         revert_options: Option<RevertOptions>,
     ) -> Result<()> {
-        instructions::deposit::handle_sol(ctx, amount, receiver, revert_options, DEPOSIT_FEE)
+        instructions::deposit::handle_sol(ctx, amount, receiver, revert_options)
     }
 
     /// Deposits SOL and calls a contract on ZetaChain zEVM.
@@ -298,16 +751,69 @@ pub mod gateway {
         message: Vec<u8>,
         revert_options: Option<RevertOptions>,
     ) -> Result<()> {
-        instructions::deposit::handle_sol_with_call(
+        instructions::deposit::handle_sol_with_call(ctx, amount, receiver, message, revert_options)
+    }
+
+    /// Deposits SOL and several SPL tokens as a single inbound charged a single deposit fee,
+    /// crediting the `receiver` on ZetaChain zEVM and optionally calling it.
+    /// The fee is the highest of the SOL deposit fee, when SOL is deposited, and the deposit fees of the SPL token mints.
+    /// Each SPL token asset is described by the remaining accounts
+    /// [whitelist entry, mint, source token account, gateway ATA, token program], in the order of `assets`.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `sol_amount` - The amount of SOL to deposit, zero to deposit only SPL tokens.
+    /// * `assets` - The SPL token mints and amounts to deposit, up to `MAX_DEPOSIT_ASSETS`.
+    /// * `receiver` - The Ethereum address of the receiver on ZetaChain zEVM.
+    /// * `message` - The message passed to the contract, `None` to not call it.
+    /// * `revert_options` - The revert options created by the caller.
+    pub fn deposit_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositMulti<'info>>,
+        sol_amount: u64,
+        assets: Vec<DepositAsset>,
+        receiver: [u8; 20],
+        message: Option<Vec<u8>>,
+        revert_options: Option<RevertOptions>,
+    ) -> Result<()> {
+        instructions::deposit::handle_multi(
             ctx,
-            amount,
+            sol_amount,
+            assets,
             receiver,
             message,
             revert_options,
-            DEPOSIT_FEE,
         )
     }
 
+    /// Unwraps a wrapped SOL account of the signer and deposits its whole balance as SOL,
+    /// crediting the `receiver` on ZetaChain zEVM. The wrapped SOL account is closed.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `receiver` - The Ethereum address of the receiver on ZetaChain zEVM.
+    /// * `revert_options` - The revert options created by the caller.
+    pub fn deposit_wsol(
+        ctx: Context<DepositWsol>,
+        receiver: [u8; 20],
+        revert_options: Option<RevertOptions>,
+    ) -> Result<()> {
+        instructions::deposit::handle_wsol(ctx, receiver, revert_options)
+    }
+
+    /// Unwraps a wrapped SOL account of the signer, deposits its whole balance as SOL
+    /// and calls a contract on ZetaChain zEVM. The wrapped SOL account is closed.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `receiver` - The Ethereum address of the receiver on ZetaChain zEVM.
+    /// * `message` - The message passed to the contract.
+    /// * `revert_options` - The revert options created by the caller.
+    pub fn deposit_wsol_and_call(
+        ctx: Context<DepositWsol>,
+        receiver: [u8; 20],
+        message: Vec<u8>,
+        revert_options: Option<RevertOptions>,
+    ) -> Result<()> {
+        instructions::deposit::handle_wsol_with_call(ctx, receiver, message, revert_options)
+    }
+
     /// Deposits SPL tokens and credits the `receiver` on ZetaChain zEVM.
     /// # Arguments
     /// * `ctx` - The instruction context.
@@ -320,7 +826,7 @@ pub mod gateway {
         receiver: [u8; 20],
         revert_options: Option<RevertOptions>,
     ) -> Result<()> {
-        instructions::deposit::handle_spl(ctx, amount, receiver, revert_options, DEPOSIT_FEE)
+        instructions::deposit::handle_spl(ctx, amount, receiver, revert_options)
     }
 
     /// Deposits SPL tokens and calls a contract on ZetaChain zEVM.
@@ -337,13 +843,64 @@ pub mod gateway {
         message: Vec<u8>,
         revert_options: Option<RevertOptions>,
     ) -> Result<()> {
-        instructions::deposit::handle_spl_with_call(
+        instructions::deposit::handle_spl_with_call(ctx, amount, receiver, message, revert_options)
+    }
+
+    /// Swaps a token through the configured swap program into a whitelisted SPL token,
+    /// and deposits the output to credit the `receiver` on ZetaChain zEVM.
+    /// The accounts of the swap instruction are passed as remaining accounts, and cannot include
+    /// the gateway program so the swap cannot reenter the gateway.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount_in` - The maximum amount of the source token to swap.
+    /// * `minimum_amount_out` - The minimum amount of the output token to deposit.
+    /// * `swap_data` - The instruction data of the swap instruction.
+    /// * `receiver` - The Ethereum address of the receiver on ZetaChain zEVM.
+    /// * `revert_options` - The revert options created by the caller.
+    pub fn deposit_with_swap(
+        ctx: Context<DepositWithSwap>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        swap_data: Vec<u8>,
+        receiver: [u8; 20],
+        revert_options: Option<RevertOptions>,
+    ) -> Result<()> {
+        instructions::deposit::handle_spl_with_swap(
             ctx,
-            amount,
+            amount_in,
+            minimum_amount_out,
+            swap_data,
+            receiver,
+            revert_options,
+        )
+    }
+
+    /// Swaps a token through the configured swap program into SOL,
+    /// and deposits the output to credit the `receiver` on ZetaChain zEVM.
+    /// The swap program must pay the output to the PDA, passed among the remaining accounts,
+    /// which cannot include the gateway program so the swap cannot reenter the gateway.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount_in` - The maximum amount of the source token to swap.
+    /// * `minimum_amount_out` - The minimum amount of lamports to deposit.
+    /// * `swap_data` - The instruction data of the swap instruction.
+    /// * `receiver` - The Ethereum address of the receiver on ZetaChain zEVM.
+    /// * `revert_options` - The revert options created by the caller.
+    pub fn deposit_sol_with_swap(
+        ctx: Context<DepositSolWithSwap>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        swap_data: Vec<u8>,
+        receiver: [u8; 20],
+        revert_options: Option<RevertOptions>,
+    ) -> Result<()> {
+        instructions::deposit::handle_sol_with_swap(
+            ctx,
+            amount_in,
+            minimum_amount_out,
+            swap_data,
             receiver,
-            message,
             revert_options,
-            DEPOSIT_FEE,
         )
     }
 
@@ -361,6 +918,96 @@ pub mod gateway {
         instructions::deposit::handle_call(ctx, receiver, message, revert_options)
     }
 
+    /// Deposits SOL into the program and credits the `receiver` on its target chain through ZetaChain.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount` - The amount of lamports to deposit.
+    /// * `receiver` - The receiver address, its address type and target chain ID.
+    /// * `revert_options` - The revert options created by the caller.
+    pub fn deposit_v2(
+        ctx: Context<Deposit>,
+        amount: u64,
+        receiver: Receiver,
+        revert_options: Option<RevertOptions>,
+    ) -> Result<()> {
+        instructions::deposit::handle_sol_v2(ctx, amount, receiver, revert_options)
+    }
+
+    /// Deposits SOL and calls the `receiver` on its target chain through ZetaChain.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount` - The amount of lamports to deposit.
+    /// * `receiver` - The receiver address, its address type and target chain ID.
+    /// * `message` - The message passed to the receiver.
+    /// * `revert_options` - The revert options created by the caller.
+    pub fn deposit_and_call_v2(
+        ctx: Context<Deposit>,
+        amount: u64,
+        receiver: Receiver,
+        message: Vec<u8>,
+        revert_options: Option<RevertOptions>,
+    ) -> Result<()> {
+        instructions::deposit::handle_sol_with_call_v2(
+            ctx,
+            amount,
+            receiver,
+            message,
+            revert_options,
+        )
+    }
+
+    /// Deposits SPL tokens and credits the `receiver` on its target chain through ZetaChain.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount` - The amount of SPL tokens to deposit.
+    /// * `receiver` - The receiver address, its address type and target chain ID.
+    /// * `revert_options` - The revert options created by the caller.
+    pub fn deposit_spl_token_v2(
+        ctx: Context<DepositSplToken>,
+        amount: u64,
+        receiver: Receiver,
+        revert_options: Option<RevertOptions>,
+    ) -> Result<()> {
+        instructions::deposit::handle_spl_v2(ctx, amount, receiver, revert_options)
+    }
+
+    /// Deposits SPL tokens and calls the `receiver` on its target chain through ZetaChain.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount` - The amount of SPL tokens to deposit.
+    /// * `receiver` - The receiver address, its address type and target chain ID.
+    /// * `message` - The message passed to the receiver.
+    /// * `revert_options` - The revert options created by the caller.
+    pub fn deposit_spl_token_and_call_v2(
+        ctx: Context<DepositSplToken>,
+        amount: u64,
+        receiver: Receiver,
+        message: Vec<u8>,
+        revert_options: Option<RevertOptions>,
+    ) -> Result<()> {
+        instructions::deposit::handle_spl_with_call_v2(
+            ctx,
+            amount,
+            receiver,
+            message,
+            revert_options,
+        )
+    }
+
+    /// Calls the `receiver` on its target chain through ZetaChain.
+    /// # Arguments
+    /// * `receiver` - The receiver address, its address type and target chain ID.
+    /// * `message` - The message passed to the receiver.
+    /// * `revert_options` - The revert options created by the caller.
+    pub fn call_v2(
+        ctx: Context<Call>,
+        receiver: Receiver,
+        message: Vec<u8>,
+        revert_options: Option<RevertOptions>,
+    ) -> Result<()> {
+        instructions::deposit::handle_call_v2(ctx, receiver, message, revert_options)
+    }
+
     /// Withdraws SOL. Caller is TSS.
     /// # Arguments
     /// * `ctx` - The instruction context.
@@ -380,6 +1027,33 @@ pub mod gateway {
         instructions::withdraw::handle_sol(ctx, amount, signature, recovery_id, message_hash, nonce)
     }
 
+    /// Withdraws SOL delivered as wrapped SOL to the recipient's associated token account,
+    /// which is created if needed. Caller is TSS.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `amount` - The amount of SOL to withdraw.
+    /// * `signature` - The TSS signature.
+    /// * `recovery_id` - The recovery ID for signature verification.
+    /// * `message_hash` - Message hash for signature verification.
+    /// * `nonce` - The current nonce value.
+    pub fn withdraw_wsol(
+        ctx: Context<WithdrawWsol>,
+        amount: u64,
+        signature: [u8; 64],
+        recovery_id: u8,
+        message_hash: [u8; 32],
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::withdraw::handle_wsol(
+            ctx,
+            amount,
+            signature,
+            recovery_id,
+            message_hash,
+            nonce,
+        )
+    }
+
     /// Withdraws SPL tokens. Caller is TSS.
     /// # Arguments
     /// * `ctx` - The instruction context.
@@ -409,6 +1083,18 @@ pub mod gateway {
         )
     }
 
+    /// Releases a withdrawal queued above the delay threshold once its release slot is reached.
+    /// Callable by anyone.
+    /// # Arguments
+    /// * `ctx` - The instruction context.
+    /// * `message_hash` - The message hash of the queued withdrawal.
+    pub fn finalize_withdrawal(
+        ctx: Context<FinalizeWithdrawal>,
+        message_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::withdraw::handle_finalize(ctx, message_hash)
+    }
+
     // Use the feature flag to conditionally compile the upgrade test function
     // This is used for localnet testing only and should not be included in the production build
     #[cfg(feature = "upgrade-test")]
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::utils::MAX_ADMIN_SIGNERS;
+
 /// PDA account storing program state and settings.
 #[account]
+#[derive(InitSpace)]
 pub struct Pda {
     /// The nonce to ensure each signature can only be used once.
     pub nonce: u64,
@@ -13,11 +16,172 @@ pub struct Pda {
     pub chain_id: u64,
     /// Flag to indicate whether deposits are paused.
     pub deposit_paused: bool,
+    /// Fee in lamports charged on SOL deposits.
+    pub deposit_fee: u64,
+    /// Fee in lamports charged on SPL token deposits.
+    pub spl_deposit_fee: u64,
+    /// Deposit fees in lamports collected and not yet withdrawn to the treasury.
+    pub collected_fees: u64,
+    /// The account receiving withdrawn deposit fees.
+    pub treasury: Pubkey,
+    /// The TSS address being rotated in, zero if no rotation is scheduled.
+    pub pending_tss_address: [u8; 20],
+    /// The slot from which the pending TSS address is accepted.
+    pub tss_activation_slot: u64,
+    /// The slot at which the pending TSS address replaces the current one.
+    pub tss_overlap_end_slot: u64,
+    /// The layout version of the PDA.
+    pub version: u8,
+    /// Minimum amount in lamports accepted per SOL deposit, zero if not configured.
+    pub min_deposit: u64,
+    /// The swap program `deposit_with_swap` CPIs into, default if swaps are disabled.
+    pub swap_program: Pubkey,
+    /// Reserved space for fields added in later layout versions.
+    pub reserved: [u8; 88],
+}
+
+/// Unversioned PDA layout, migrated to the current layout by `upgrade_pda`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PdaV0 {
+    pub nonce: u64,
+    pub tss_address: [u8; 20],
+    pub authority: Pubkey,
+    pub chain_id: u64,
+    pub deposit_paused: bool,
 }
 
 /// Whitelist entry account for whitelisted SPL tokens.
 #[account]
-pub struct WhitelistEntry {}
+#[derive(InitSpace)]
+pub struct WhitelistEntry {
+    /// Set when the mint is unwhitelisted: deposits are rejected while withdrawals keep working.
+    pub withdraw_only: bool,
+    /// Whether deposits of this mint are paused.
+    pub deposit_paused: bool,
+    /// Minimum amount accepted per deposit.
+    pub min_deposit: u64,
+    /// Maximum amount accepted per deposit.
+    pub max_deposit: u64,
+    /// Decimals of the mint, checked against the decimals passed by TSS on withdrawal.
+    pub decimals: u8,
+    /// Token program owning the mint, SPL Token or Token-2022.
+    pub token_program: Pubkey,
+    /// Deposit fee in lamports for this mint, `None` to use the gateway SPL deposit fee.
+    pub deposit_fee: Option<u64>,
+    /// Layout version of the entry.
+    pub version: u8,
+}
+
+/// Fee exemption entry for a partner program depositing through CPI.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeExemption {
+    /// The program exempt from deposit fees.
+    pub program_id: Pubkey,
+}
+
+/// M-of-N admin configuration for sensitive gateway operations.
+#[account]
+#[derive(InitSpace)]
+pub struct AdminConfig {
+    /// The admin signers allowed to propose and approve actions.
+    #[max_len(MAX_ADMIN_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+    /// The number of approvals required to execute an action.
+    pub threshold: u8,
+    /// The ID assigned to the next proposal.
+    pub proposal_count: u64,
+    /// Incremented whenever the signers change, invalidating proposals made by earlier signers.
+    pub signers_epoch: u64,
+}
+
+/// Admin action collecting approvals from admin signers.
+#[account]
+#[derive(InitSpace)]
+pub struct AdminProposal {
+    /// The proposal ID.
+    pub id: u64,
+    /// The admin signer who proposed the action, refunded when it is executed.
+    pub proposer: Pubkey,
+    /// The proposed action.
+    pub action: AdminAction,
+    /// The admin signers who approved the action.
+    #[max_len(MAX_ADMIN_SIGNERS)]
+    pub approvals: Vec<Pubkey>,
+    /// The signers epoch of the admin config when the action was proposed.
+    pub signers_epoch: u64,
+    /// The last slot at which the action can be approved or executed.
+    pub expiry_slot: u64,
+}
+
+/// Sensitive admin operations requiring approvals when an admin config exists.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub enum AdminAction {
+    UpdateTss {
+        tss_address: [u8; 20],
+        activation_slot: u64,
+        overlap_slots: u64,
+    },
+    ResetNonce {
+        nonce: u64,
+    },
+    UpdateAuthority {
+        authority: Pubkey,
+    },
+    UpdateSigners {
+        #[max_len(MAX_ADMIN_SIGNERS)]
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+}
+
+/// Outbound rate limit and withdrawal delay for SOL or an SPL token, applied to withdrawals and executions.
+#[account]
+#[derive(InitSpace)]
+pub struct RateLimit {
+    /// The rate limited mint, `Pubkey::default()` for SOL.
+    pub mint: Pubkey,
+    /// Maximum amount released by a single outbound.
+    pub max_per_withdrawal: u64,
+    /// Maximum amount released within a rolling window.
+    pub max_per_window: u64,
+    /// Length of the rolling window in slots.
+    pub window_slots: u64,
+    /// Amount counted in the rolling window, released linearly over `window_slots`.
+    pub window_amount: u64,
+    /// The slot at which `window_amount` was last updated.
+    pub last_update_slot: u64,
+    /// Withdrawals above this amount are queued instead of released immediately.
+    pub delay_threshold: u64,
+    /// Number of slots a queued withdrawal waits before it can be finalized, zero disables queueing.
+    pub delay_slots: u64,
+}
+
+/// Withdrawal above the delay threshold, waiting to be finalized or cancelled.
+#[account]
+#[derive(InitSpace)]
+pub struct PendingWithdrawal {
+    /// The nonce of the withdrawal, consumed when it was queued.
+    pub nonce: u64,
+    /// The TSS-signed message hash of the withdrawal, which derives the pending withdrawal PDA.
+    pub message_hash: [u8; 32],
+    /// The withdrawn mint, `None` for SOL.
+    pub mint: Option<Pubkey>,
+    /// The mint keying the rate limit window the withdrawal consumed, `Pubkey::default()` for SOL and wrapped SOL.
+    pub rate_limit_mint: Pubkey,
+    /// The recipient of the withdrawal.
+    pub recipient: Pubkey,
+    /// The recipient's associated token account, `Pubkey::default()` for SOL.
+    pub recipient_ata: Pubkey,
+    /// The withdrawn amount.
+    pub amount: u64,
+    /// The decimals of the withdrawn mint.
+    pub decimals: u8,
+    /// The slot from which the withdrawal can be finalized.
+    pub release_slot: u64,
+    /// The account that paid the rent, refunded when the withdrawal is finalized or cancelled.
+    pub payer: Pubkey,
+}
 
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq)]
@@ -89,7 +253,80 @@ pub struct RevertOptions {
     pub on_revert_gas_limit: u64,
 }
 
+/// Address format of a receiver targeted by versioned deposits and calls.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressType {
+    /// 20-byte zEVM or EVM chain address.
+    Evm,
+    /// 32-byte Solana pubkey.
+    Solana,
+    /// Bitcoin address string, base58 or bech32 encoded.
+    Bitcoin,
+}
+
+/// Struct containing the receiver of a versioned deposit or call
+/// # Arguments
+/// * `address_type` Format of the address, which defines how it is validated.
+/// * `address` Address of the receiver, up to `MAX_RECEIVER_ADDRESS_SIZE` bytes.
+/// * `chain_id` Chain ID of the chain the receiver lives on, routed through ZetaChain.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Receiver {
+    pub address_type: AddressType,
+    pub address: Vec<u8>,
+    pub chain_id: u64,
+}
+
+/// Struct containing an SPL token asset of a multi-asset deposit
+/// # Arguments
+/// * `mint` Mint of the deposited SPL token.
+/// * `amount` Amount of the SPL token, the amount received by the gateway in events.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DepositAsset {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Struct containing an account expected by a destination program, published in its expected accounts PDA
+/// # Arguments
+/// * `pubkey` Address of the account forwarded to the destination program.
+/// * `is_writable` Whether the account is forwarded as writable.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ExpectedAccount {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
+}
+
+/// Policy applied to an account forwarded to the destination program of an execute.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ForwardedAccountPolicy {
+    /// The account cannot be forwarded.
+    Deny,
+    /// The account is always forwarded as read-only.
+    ReadOnly,
+    /// The account is the gateway caller PDA, forwarded as a read-only signer.
+    GatewaySigner,
+    /// The account is forwarded with the writability chosen by the relayer.
+    AsRequested,
+}
+
+/// Typed failure code of a failed outbound, reported by TSS when incrementing the nonce.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum FailureCode {
+    /// The destination program call failed.
+    CallFailed = 0,
+    /// The outbound exceeded its compute budget.
+    ComputeBudgetExceeded = 1,
+    /// The outbound accounts were missing or invalid.
+    InvalidAccounts = 2,
+    /// The outbound was rejected by the gateway, for example by a rate limit or a withdraw-only mint.
+    Rejected = 3,
+    /// Any other failure.
+    Other = 4,
+}
+
 /// Enumeration for instruction identifiers in message hashes.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 #[repr(u8)]
 pub enum InstructionId {
     Withdraw = 1,
@@ -101,4 +338,11 @@ pub enum InstructionId {
     IncrementNonce = 7,
     ExecuteSolRevert = 8,
     ExecuteSplTokenRevert = 9,
+    UpdateDepositFee = 10,
+    WithdrawWsol = 11,
+    ExecuteSolV2 = 12,
+    ExecuteSplTokenV2 = 13,
+    ExecuteSolRevertV2 = 14,
+    ExecuteSplTokenRevertV2 = 15,
+    IncrementNonceV2 = 16,
 }
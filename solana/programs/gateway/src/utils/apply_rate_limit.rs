@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::Errors;
+use crate::state::RateLimit;
+
+/// Records an outbound `amount` against the rate limit account, if one is configured
+///
+/// # Arguments
+///
+/// * `rate_limit` - The rate limit PDA of the asset, uninitialized if the asset is not rate limited
+/// * `amount` - The amount released by the outbound
+///
+/// # Returns
+///
+/// * `Result<u64>` - The number of slots the outbound must be delayed, zero if it can be released now
+///
+/// # Errors
+///
+/// Returns `Errors::WithdrawalAboveLimit` or `Errors::RateLimitExceeded` if the amount is over the limit
+pub fn apply_rate_limit(rate_limit: &AccountInfo, amount: u64) -> Result<u64> {
+    if rate_limit.data_is_empty() {
+        return Ok(0);
+    }
+    require_keys_eq!(
+        *rate_limit.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+
+    let mut data = rate_limit.try_borrow_mut_data()?;
+    let mut limit = RateLimit::try_deserialize(&mut &data[..])?;
+    consume_rate_limit(&mut limit, amount, Clock::get()?.slot)?;
+    limit.try_serialize(&mut &mut data[..])?;
+
+    if limit.delay_slots > 0 && amount > limit.delay_threshold {
+        Ok(limit.delay_slots)
+    } else {
+        Ok(0)
+    }
+}
+
+/// Releases the part of the window amount that has decayed since the last update and adds `amount`
+pub fn consume_rate_limit(limit: &mut RateLimit, amount: u64, slot: u64) -> Result<()> {
+    require!(
+        amount <= limit.max_per_withdrawal,
+        Errors::WithdrawalAboveLimit
+    );
+
+    let elapsed = slot.saturating_sub(limit.last_update_slot) as u128;
+    let released = (limit.max_per_window as u128 * elapsed / limit.window_slots as u128)
+        .min(u64::MAX as u128) as u64;
+    let window_amount = limit
+        .window_amount
+        .saturating_sub(released)
+        .checked_add(amount)
+        .ok_or(Errors::RateLimitExceeded)?;
+    require!(
+        window_amount <= limit.max_per_window,
+        Errors::RateLimitExceeded
+    );
+
+    limit.window_amount = window_amount;
+    limit.last_update_slot = slot;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limit() -> RateLimit {
+        RateLimit {
+            mint: Pubkey::default(),
+            max_per_withdrawal: 100,
+            max_per_window: 250,
+            window_slots: 1000,
+            window_amount: 0,
+            last_update_slot: 0,
+            delay_threshold: 0,
+            delay_slots: 0,
+        }
+    }
+
+    #[test]
+    fn test_consume_rate_limit_above_max_per_withdrawal() {
+        // Arrange
+        let mut limit = rate_limit();
+
+        // Act
+        let result = consume_rate_limit(&mut limit, 101, 10);
+
+        // Assert
+        assert_eq!(result.err(), Some(Errors::WithdrawalAboveLimit.into()));
+    }
+
+    #[test]
+    fn test_consume_rate_limit_window_exceeded() {
+        // Arrange
+        let mut limit = rate_limit();
+        consume_rate_limit(&mut limit, 100, 10).unwrap();
+        consume_rate_limit(&mut limit, 100, 10).unwrap();
+
+        // Act
+        let result = consume_rate_limit(&mut limit, 100, 10);
+
+        // Assert
+        assert_eq!(result.err(), Some(Errors::RateLimitExceeded.into()));
+        assert_eq!(limit.window_amount, 200);
+    }
+
+    #[test]
+    fn test_consume_rate_limit_window_releases_over_time() {
+        // Arrange
+        let mut limit = rate_limit();
+        consume_rate_limit(&mut limit, 100, 10).unwrap();
+        consume_rate_limit(&mut limit, 100, 10).unwrap();
+
+        // Act
+        // 400 slots release 100 of the 250 window
+        let result = consume_rate_limit(&mut limit, 100, 410);
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(limit.window_amount, 200);
+        assert_eq!(limit.last_update_slot, 410);
+    }
+}
@@ -1,3 +1,5 @@
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+
 /// Prefix used for outbounds message hashes.
 pub const ZETACHAIN_PREFIX: &[u8] = b"ZETACHAIN";
 
@@ -7,5 +9,51 @@ pub const DEFAULT_GAS_COST: u64 = 5000;
 // Maximum size of a message payload in bytes
 pub const MAX_DEPOSIT_PAYLOAD_SIZE: usize = 745;
 
+/// Maximum size of the data returned by a destination program emitted in `ExecuteCompleted`, longer data is truncated.
+pub const MAX_EXECUTE_RETURN_DATA_SIZE: usize = 512;
+
+/// Maximum size of a receiver address in versioned deposits and calls, the bech32 limit.
+pub const MAX_RECEIVER_ADDRESS_SIZE: usize = 90;
+
+/// Minimum size of a Bitcoin receiver address, the shortest base58 address.
+pub const MIN_BITCOIN_ADDRESS_SIZE: usize = 26;
+
+/// Maximum number of SPL token assets in a multi-asset deposit.
+pub const MAX_DEPOSIT_ASSETS: usize = 4;
+
+/// Number of remaining accounts describing each SPL token asset of a multi-asset deposit:
+/// whitelist entry, mint, source token account, gateway ATA and token program.
+pub const DEPOSIT_ASSET_ACCOUNTS: usize = 5;
+
 /// Deposit fee used when depositing SOL or SPL tokens.
 pub const DEPOSIT_FEE: u64 = 2_000_000;
+
+/// Maximum deposit fee in lamports that can be configured for SOL or SPL token deposits.
+pub const MAX_DEPOSIT_FEE: u64 = 100_000_000;
+
+/// Current layout version of the gateway PDA.
+pub const PDA_VERSION: u8 = 1;
+
+/// Size of the unversioned gateway PDA, which was sized with `size_of` at initialization.
+pub const LEGACY_PDA_SPACE: usize = 8 + std::mem::size_of::<crate::state::PdaV0>();
+
+/// Current layout version of whitelist entries.
+pub const WHITELIST_ENTRY_VERSION: u8 = 1;
+
+/// Size of an unversioned whitelist entry, which only held the discriminator.
+pub const LEGACY_WHITELIST_ENTRY_SPACE: usize = 8;
+
+/// Maximum number of admin signers in the admin config.
+pub const MAX_ADMIN_SIGNERS: usize = 10;
+
+/// Number of slots an admin proposal can be approved and executed for, about one day.
+pub const ADMIN_PROPOSAL_EXPIRY_SLOTS: u64 = 216_000;
+
+/// Token-2022 mint extensions the gateway cannot custody safely.
+/// Non-transferable tokens cannot be withdrawn, a permanent delegate can move tokens out of the
+/// gateway ATA, and transfer hooks require extra accounts the gateway does not forward.
+pub const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::NonTransferable,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::TransferHook,
+];
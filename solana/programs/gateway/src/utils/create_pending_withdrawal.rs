@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::state::PendingWithdrawal;
+
+/// Creates the pending withdrawal PDA for `pending.message_hash`, funded by `payer`, and stores `pending` in it
+///
+/// # Arguments
+///
+/// * `pending_withdrawal` - The pending withdrawal PDA, derived from the message hash
+/// * `payer` - The account paying the rent
+/// * `system_program` - The system program
+/// * `bump` - The bump of the pending withdrawal PDA
+/// * `pending` - The queued withdrawal
+pub fn create_pending_withdrawal<'info>(
+    pending_withdrawal: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    bump: u8,
+    pending: &PendingWithdrawal,
+) -> Result<()> {
+    let space = 8 + PendingWithdrawal::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer_seeds: &[&[&[u8]]] = &[&[b"pending_withdrawal", &pending.message_hash, &[bump]]];
+
+    // the address may already hold lamports, in which case it cannot be created in one step
+    let current_lamports = pending_withdrawal.lamports();
+    if current_lamports == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: pending_withdrawal.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        let shortfall = rent.saturating_sub(current_lamports);
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: pending_withdrawal.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Allocate {
+                    account_to_allocate: pending_withdrawal.clone(),
+                },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Assign {
+                    account_to_assign: pending_withdrawal.clone(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    pending.try_serialize(&mut &mut pending_withdrawal.try_borrow_mut_data()?[..])
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use spl_associated_token_account::instruction::create_associated_token_account;
+
+/// Creates the associated token account of `recipient` for `mint` if it does not exist yet, funded by `payer`
+///
+/// # Arguments
+///
+/// * `payer` - The account paying the rent
+/// * `recipient` - The owner of the associated token account
+/// * `recipient_ata` - The associated token account, already verified to match the recipient and mint
+/// * `mint` - The mint of the associated token account
+/// * `system_program` - The system program
+/// * `token_program` - The token program of the mint
+/// * `associated_token_program` - The associated token program
+///
+/// # Returns
+///
+/// The rent in lamports paid by `payer`, zero if the account already exists
+pub fn create_recipient_ata<'info>(
+    payer: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    recipient_ata: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+) -> Result<u64> {
+    if recipient_ata.lamports() != 0 && *recipient_ata.owner != system_program.key() {
+        return Ok(0);
+    }
+
+    msg!(
+        "Creating associated token account {:?} for recipient {:?}...",
+        recipient_ata.key(),
+        recipient.key(),
+    );
+
+    let bal_before = payer.lamports();
+    invoke(
+        &create_associated_token_account(payer.key, recipient.key, mint.key, token_program.key),
+        &[
+            mint.clone(),
+            recipient_ata.clone(),
+            recipient.clone(),
+            payer.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )?;
+    let bal_after = payer.lamports();
+    let cost_ata_create = bal_before - bal_after;
+
+    msg!("Associated token account created!");
+    msg!(
+        "Refunding the rent ({:?} lamports) paid by the signer {:?}",
+        cost_ata_create,
+        payer.key
+    );
+
+    Ok(cost_ata_create)
+}
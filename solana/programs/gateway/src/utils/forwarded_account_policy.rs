@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::ForwardedAccountPolicy;
+
+/// Resolves the policy applied to an account forwarded to the destination program of an execute
+///
+/// # Arguments
+///
+/// * `account_info` - The forwarded account
+/// * `signer` - The signer of the execute, which cannot be forwarded
+/// * `pda` - The gateway PDA
+/// * `caller` - The gateway caller PDA
+///
+/// # Returns
+///
+/// * `ForwardedAccountPolicy::Deny` for the signer
+/// * `ForwardedAccountPolicy::GatewaySigner` for the gateway caller PDA
+/// * `ForwardedAccountPolicy::ReadOnly` for accounts owned by the gateway program, including the
+///   gateway PDA, and for token accounts whose authority is the gateway PDA
+/// * `ForwardedAccountPolicy::AsRequested` otherwise
+pub fn forwarded_account_policy(
+    account_info: &AccountInfo,
+    signer: &Pubkey,
+    pda: &Pubkey,
+    caller: &Pubkey,
+) -> ForwardedAccountPolicy {
+    if account_info.key == signer {
+        return ForwardedAccountPolicy::Deny;
+    }
+
+    if account_info.key == caller {
+        return ForwardedAccountPolicy::GatewaySigner;
+    }
+
+    if account_info.key == pda || *account_info.owner == crate::ID {
+        return ForwardedAccountPolicy::ReadOnly;
+    }
+
+    if *account_info.owner == spl_token::ID || *account_info.owner == spl_token_2022::ID {
+        let is_gateway_token_account = account_info
+            .try_borrow_data()
+            .ok()
+            .and_then(|data| TokenAccount::try_deserialize_unchecked(&mut &data[..]).ok())
+            .is_some_and(|token_account| token_account.owner == *pda);
+        if is_gateway_token_account {
+            return ForwardedAccountPolicy::ReadOnly;
+        }
+    }
+
+    ForwardedAccountPolicy::AsRequested
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use spl_token::state::{Account, AccountState};
+
+    fn token_account_data(owner: &Pubkey) -> Vec<u8> {
+        let mut data = vec![0u8; Account::LEN];
+        Account {
+            mint: Pubkey::new_unique(),
+            owner: *owner,
+            state: AccountState::Initialized,
+            ..Account::default()
+        }
+        .pack_into_slice(&mut data);
+        data
+    }
+
+    const DESTINATION_PROGRAM: Pubkey = Pubkey::new_from_array([7; 32]);
+
+    fn caller(destination_program: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"caller", destination_program.as_ref()], &crate::ID).0
+    }
+
+    fn policy(
+        key: &Pubkey,
+        owner: &Pubkey,
+        data: &mut [u8],
+        signer: &Pubkey,
+        pda: &Pubkey,
+    ) -> ForwardedAccountPolicy {
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(key, false, true, &mut lamports, data, owner, false, 0);
+        forwarded_account_policy(&account_info, signer, pda, &caller(&DESTINATION_PROGRAM))
+    }
+
+    #[test]
+    fn test_forwarded_account_policy_signer_and_gateway_accounts() {
+        // Arrange
+        let signer = Pubkey::new_unique();
+        let pda = Pubkey::new_unique();
+        let gateway_account = Pubkey::new_unique();
+        let other_caller = caller(&Pubkey::new_unique());
+
+        // Act
+        let signer_policy = policy(&signer, &Pubkey::default(), &mut [], &signer, &pda);
+        let caller_policy = policy(
+            &caller(&DESTINATION_PROGRAM),
+            &Pubkey::default(),
+            &mut [],
+            &signer,
+            &pda,
+        );
+        let other_caller_policy = policy(&other_caller, &Pubkey::default(), &mut [], &signer, &pda);
+        let pda_policy = policy(&pda, &crate::ID, &mut [], &signer, &pda);
+        let gateway_account_policy = policy(&gateway_account, &crate::ID, &mut [], &signer, &pda);
+
+        // Assert
+        assert_eq!(signer_policy, ForwardedAccountPolicy::Deny);
+        assert_eq!(caller_policy, ForwardedAccountPolicy::GatewaySigner);
+        assert_eq!(other_caller_policy, ForwardedAccountPolicy::AsRequested);
+        assert_eq!(pda_policy, ForwardedAccountPolicy::ReadOnly);
+        assert_eq!(gateway_account_policy, ForwardedAccountPolicy::ReadOnly);
+    }
+
+    #[test]
+    fn test_forwarded_account_policy_token_accounts() {
+        // Arrange
+        let signer = Pubkey::new_unique();
+        let pda = Pubkey::new_unique();
+        let mut gateway_ata = token_account_data(&pda);
+        let mut gateway_ata_2022 = token_account_data(&pda);
+        let mut other_ata = token_account_data(&Pubkey::new_unique());
+
+        // Act
+        let gateway_ata_policy = policy(
+            &Pubkey::new_unique(),
+            &spl_token::ID,
+            &mut gateway_ata,
+            &signer,
+            &pda,
+        );
+        let gateway_ata_2022_policy = policy(
+            &Pubkey::new_unique(),
+            &spl_token_2022::ID,
+            &mut gateway_ata_2022,
+            &signer,
+            &pda,
+        );
+        let other_ata_policy = policy(
+            &Pubkey::new_unique(),
+            &spl_token::ID,
+            &mut other_ata,
+            &signer,
+            &pda,
+        );
+
+        // Assert
+        assert_eq!(gateway_ata_policy, ForwardedAccountPolicy::ReadOnly);
+        assert_eq!(gateway_ata_2022_policy, ForwardedAccountPolicy::ReadOnly);
+        assert_eq!(other_ata_policy, ForwardedAccountPolicy::AsRequested);
+    }
+
+    #[test]
+    fn test_forwarded_account_policy_other_accounts() {
+        // Arrange
+        let signer = Pubkey::new_unique();
+        let pda = Pubkey::new_unique();
+        // token account data owned by another program is never trusted
+        let mut spoofed_ata = token_account_data(&pda);
+
+        // Act
+        let wallet_policy = policy(
+            &Pubkey::new_unique(),
+            &Pubkey::default(),
+            &mut [],
+            &signer,
+            &pda,
+        );
+        let spoofed_ata_policy = policy(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &mut spoofed_ata,
+            &signer,
+            &pda,
+        );
+
+        // Assert
+        assert_eq!(wallet_policy, ForwardedAccountPolicy::AsRequested);
+        assert_eq!(spoofed_ata_policy, ForwardedAccountPolicy::AsRequested);
+    }
+}
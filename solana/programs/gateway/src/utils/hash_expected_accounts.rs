@@ -0,0 +1,52 @@
+use anchor_lang::solana_program::keccak::hash;
+
+use crate::state::ExpectedAccount;
+
+/// Hashes an ordered account list, committing to the key and writability of each account
+///
+/// # Arguments
+///
+/// * `accounts` - The ordered account list
+///
+/// # Returns
+///
+/// * `[u8; 32]` - The keccak256 hash of the concatenated account keys, each followed by `1` if the
+///   account is writable and `0` otherwise
+pub fn hash_expected_accounts(accounts: &[ExpectedAccount]) -> [u8; 32] {
+    let mut concatenated_buffer = Vec::with_capacity(accounts.len() * 33);
+
+    for account in accounts {
+        concatenated_buffer.extend_from_slice(&account.pubkey.to_bytes());
+        concatenated_buffer.push(account.is_writable as u8);
+    }
+
+    hash(&concatenated_buffer).to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+
+    #[test]
+    fn test_hash_expected_accounts_commits_to_writability_and_order() {
+        // Arrange
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        let account = |pubkey: Pubkey, is_writable: bool| ExpectedAccount {
+            pubkey,
+            is_writable,
+        };
+
+        // Act
+        let hash = hash_expected_accounts(&[account(first, true), account(second, false)]);
+        let readonly_hash =
+            hash_expected_accounts(&[account(first, false), account(second, false)]);
+        let reordered_hash =
+            hash_expected_accounts(&[account(second, false), account(first, true)]);
+
+        // Assert
+        assert_ne!(hash, readonly_hash);
+        assert_ne!(hash, reordered_hash);
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::Errors;
+use crate::state::ExpectedAccount;
+
+/// Loads the account list published by a destination program in its expected accounts PDA.
+/// The PDA holds an 8-byte discriminator followed by the Borsh-serialized `Vec<ExpectedAccount>`.
+///
+/// # Arguments
+///
+/// * `expected_accounts` - The expected accounts PDA of the destination program
+/// * `destination_program` - The destination program
+///
+/// # Returns
+///
+/// * `Result<Option<Vec<ExpectedAccount>>>` - The published account list, `None` if the destination
+///   program has not published one
+///
+/// # Errors
+///
+/// Returns `Errors::InvalidExpectedAccounts` if the published account list cannot be deserialized
+pub fn load_expected_accounts(
+    expected_accounts: &AccountInfo,
+    destination_program: &Pubkey,
+) -> Result<Option<Vec<ExpectedAccount>>> {
+    if expected_accounts.owner != destination_program || expected_accounts.data_is_empty() {
+        return Ok(None);
+    }
+
+    let data = expected_accounts.try_borrow_data()?;
+    let list = data
+        .get(8..)
+        .and_then(|mut list| Vec::<ExpectedAccount>::deserialize(&mut list).ok())
+        .ok_or(Errors::InvalidExpectedAccounts)?;
+
+    Ok(Some(list))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(
+        owner: &Pubkey,
+        data: &mut [u8],
+        destination_program: &Pubkey,
+    ) -> Result<Option<Vec<ExpectedAccount>>> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account_info =
+            AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+        load_expected_accounts(&account_info, destination_program)
+    }
+
+    #[test]
+    fn test_load_expected_accounts_published() {
+        // Arrange
+        let destination_program = Pubkey::new_unique();
+        let list = vec![ExpectedAccount {
+            pubkey: Pubkey::new_unique(),
+            is_writable: true,
+        }];
+        let mut data = vec![0u8; 8];
+        list.serialize(&mut data).unwrap();
+
+        // Act
+        let result = load(&destination_program, &mut data, &destination_program);
+
+        // Assert
+        assert_eq!(result.unwrap(), Some(list));
+    }
+
+    #[test]
+    fn test_load_expected_accounts_not_published() {
+        // Arrange
+        let destination_program = Pubkey::new_unique();
+        let mut data = vec![0u8; 12];
+
+        // Act
+        let uninitialized_result = load(&Pubkey::default(), &mut [], &destination_program);
+        let other_owner_result = load(&Pubkey::new_unique(), &mut data, &destination_program);
+
+        // Assert
+        assert_eq!(uninitialized_result.unwrap(), None);
+        assert_eq!(other_owner_result.unwrap(), None);
+    }
+
+    #[test]
+    fn test_load_expected_accounts_invalid() {
+        // Arrange
+        let destination_program = Pubkey::new_unique();
+        let mut data = vec![0u8; 4];
+
+        // Act
+        let result = load(&destination_program, &mut data, &destination_program);
+
+        // Assert
+        assert_eq!(result.err(), Some(Errors::InvalidExpectedAccounts.into()));
+    }
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use super::constants::MAX_EXECUTE_RETURN_DATA_SIZE;
+
+/// Load the return data left after calling the destination program of an execute
+///
+/// # Arguments
+///
+/// * `return_data` - The program and data returned by `get_return_data`, if any
+/// * `destination_program` - The destination program of the execute
+///
+/// # Returns
+///
+/// * `(Vec<u8>, bool)` - The data returned by the destination program, empty if it returned none
+///   or if the return data was set by another program, and whether it was truncated to
+///   `MAX_EXECUTE_RETURN_DATA_SIZE` bytes
+pub fn load_return_data(
+    return_data: Option<(Pubkey, Vec<u8>)>,
+    destination_program: &Pubkey,
+) -> (Vec<u8>, bool) {
+    let Some((program_id, mut data)) = return_data else {
+        return (Vec::new(), false);
+    };
+    if program_id != *destination_program {
+        return (Vec::new(), false);
+    }
+
+    let truncated = data.len() > MAX_EXECUTE_RETURN_DATA_SIZE;
+    data.truncate(MAX_EXECUTE_RETURN_DATA_SIZE);
+
+    (data, truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_return_data_from_destination_program() {
+        // Arrange
+        let destination_program = Pubkey::new_unique();
+        let data = vec![1u8; MAX_EXECUTE_RETURN_DATA_SIZE];
+
+        // Act
+        let result = load_return_data(
+            Some((destination_program, data.clone())),
+            &destination_program,
+        );
+
+        // Assert
+        assert_eq!(result, (data, false));
+    }
+
+    #[test]
+    fn test_load_return_data_missing_or_from_other_program() {
+        // Arrange
+        let destination_program = Pubkey::new_unique();
+
+        // Act
+        let missing_result = load_return_data(None, &destination_program);
+        let other_program_result = load_return_data(
+            Some((Pubkey::new_unique(), vec![1u8; 8])),
+            &destination_program,
+        );
+
+        // Assert
+        assert_eq!(missing_result, (Vec::new(), false));
+        assert_eq!(other_program_result, (Vec::new(), false));
+    }
+
+    #[test]
+    fn test_load_return_data_too_large() {
+        // Arrange
+        let destination_program = Pubkey::new_unique();
+        let data = vec![1u8; MAX_EXECUTE_RETURN_DATA_SIZE + 1];
+
+        // Act
+        let result = load_return_data(Some((destination_program, data)), &destination_program);
+
+        // Assert
+        assert_eq!(result, (vec![1u8; MAX_EXECUTE_RETURN_DATA_SIZE], true));
+    }
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::state::WhitelistEntry;
+use crate::utils::LEGACY_WHITELIST_ENTRY_SPACE;
+
+/// Loads the per-mint settings of a whitelist entry for the withdraw path.
+/// Withdrawals do not depend on the entry, so entries that were never created, were closed or
+/// still use the unversioned layout are accepted without settings.
+///
+/// # Arguments
+///
+/// * `whitelist_entry` - The whitelist entry PDA of the mint
+///
+/// # Returns
+///
+/// * `Result<Option<WhitelistEntry>>` - The entry, `None` if it is missing or not yet migrated
+///
+/// # Errors
+///
+/// Returns an error if the entry is not owned by the gateway or cannot be deserialized
+pub fn load_whitelist_entry(whitelist_entry: &AccountInfo) -> Result<Option<WhitelistEntry>> {
+    if whitelist_entry.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(
+        *whitelist_entry.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+
+    let data = whitelist_entry.try_borrow_data()?;
+    if data.len() == LEGACY_WHITELIST_ENTRY_SPACE {
+        return Ok(None);
+    }
+
+    WhitelistEntry::try_deserialize(&mut &data[..]).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    fn load(owner: &Pubkey, data: &mut [u8]) -> Result<Option<WhitelistEntry>> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+        load_whitelist_entry(&account)
+    }
+
+    #[test]
+    fn test_load_whitelist_entry_current_layout() {
+        // Arrange
+        let entry = WhitelistEntry {
+            withdraw_only: true,
+            deposit_paused: false,
+            min_deposit: 1,
+            max_deposit: 2,
+            decimals: 6,
+            token_program: anchor_spl::token::ID,
+            deposit_fee: None,
+            version: 1,
+        };
+        let mut data = [0u8; 8 + WhitelistEntry::INIT_SPACE];
+        entry.try_serialize(&mut &mut data[..]).unwrap();
+
+        // Act
+        let loaded = load(&crate::ID, &mut data).unwrap().unwrap();
+
+        // Assert
+        assert!(loaded.withdraw_only);
+        assert_eq!(loaded.decimals, 6);
+        assert_eq!(loaded.token_program, anchor_spl::token::ID);
+    }
+
+    #[test]
+    fn test_load_whitelist_entry_missing_or_legacy() {
+        // Arrange
+        let mut empty: [u8; 0] = [];
+        let mut legacy = WhitelistEntry::DISCRIMINATOR.to_vec();
+
+        // Act
+        let missing = load(&Pubkey::default(), &mut empty).unwrap();
+        let legacy = load(&crate::ID, &mut legacy).unwrap();
+
+        // Assert
+        assert!(missing.is_none());
+        assert!(legacy.is_none());
+    }
+
+    #[test]
+    fn test_load_whitelist_entry_wrong_owner() {
+        // Arrange
+        let mut data = WhitelistEntry::DISCRIMINATOR.to_vec();
+
+        // Act
+        let result = load(&Pubkey::new_unique(), &mut data);
+
+        // Assert
+        assert!(result.is_err());
+    }
+}
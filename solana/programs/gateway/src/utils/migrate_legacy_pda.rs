@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::errors::Errors;
+use crate::state::{Pda, PdaV0};
+use crate::utils::{DEPOSIT_FEE, LEGACY_PDA_SPACE, PDA_VERSION};
+
+/// Deserializes PDA data stored in the unversioned layout and maps it to the current layout
+///
+/// # Arguments
+///
+/// * `data` - The account data, including the discriminator
+///
+/// # Returns
+///
+/// * `Result<Pda>` - The PDA in the current layout. Fields missing from the legacy layout take
+///   the values set by `initialize`, with the authority as treasury
+///
+/// # Errors
+///
+/// Returns `Errors::InvalidPdaLayout` if the data is not a gateway PDA and
+/// `Errors::PdaAlreadyUpgraded` if it does not use the unversioned layout
+pub fn migrate_legacy_pda(data: &[u8]) -> Result<Pda> {
+    require!(
+        data.len() >= 8 && data[..8] == *Pda::DISCRIMINATOR,
+        Errors::InvalidPdaLayout
+    );
+    require!(data.len() == LEGACY_PDA_SPACE, Errors::PdaAlreadyUpgraded);
+
+    let legacy =
+        PdaV0::deserialize(&mut &data[8..]).map_err(|_| error!(Errors::InvalidPdaLayout))?;
+
+    Ok(Pda {
+        nonce: legacy.nonce,
+        tss_address: legacy.tss_address,
+        authority: legacy.authority,
+        chain_id: legacy.chain_id,
+        deposit_paused: legacy.deposit_paused,
+        deposit_fee: DEPOSIT_FEE,
+        spl_deposit_fee: DEPOSIT_FEE,
+        collected_fees: 0,
+        treasury: legacy.authority,
+        pending_tss_address: [0u8; 20],
+        tss_activation_slot: 0,
+        tss_overlap_end_slot: 0,
+        version: PDA_VERSION,
+        min_deposit: 0,
+        swap_program: Pubkey::default(),
+        reserved: [0u8; 88],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_pda_data(legacy: &PdaV0) -> Vec<u8> {
+        let mut data = Pda::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        data.resize(LEGACY_PDA_SPACE, 0);
+        data
+    }
+
+    #[test]
+    fn test_migrate_legacy_pda_success() {
+        // Arrange
+        let legacy = PdaV0 {
+            nonce: 42,
+            tss_address: [7u8; 20],
+            authority: Pubkey::new_unique(),
+            chain_id: 111111,
+            deposit_paused: true,
+        };
+        let data = legacy_pda_data(&legacy);
+
+        // Act
+        let pda = migrate_legacy_pda(&data).unwrap();
+        let mut migrated_data = vec![0u8; 8 + Pda::INIT_SPACE];
+        pda.try_serialize(&mut &mut migrated_data[..]).unwrap();
+        let migrated = Pda::try_deserialize(&mut &migrated_data[..]).unwrap();
+
+        // Assert
+        assert_eq!(migrated.nonce, legacy.nonce);
+        assert_eq!(migrated.tss_address, legacy.tss_address);
+        assert_eq!(migrated.authority, legacy.authority);
+        assert_eq!(migrated.chain_id, legacy.chain_id);
+        assert!(migrated.deposit_paused);
+        assert_eq!(migrated.deposit_fee, DEPOSIT_FEE);
+        assert_eq!(migrated.spl_deposit_fee, DEPOSIT_FEE);
+        assert_eq!(migrated.collected_fees, 0);
+        assert_eq!(migrated.treasury, legacy.authority);
+        assert_eq!(migrated.pending_tss_address, [0u8; 20]);
+        assert_eq!(migrated.version, PDA_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_legacy_pda_already_upgraded() {
+        // Arrange
+        let legacy = PdaV0 {
+            nonce: 0,
+            tss_address: [1u8; 20],
+            authority: Pubkey::new_unique(),
+            chain_id: 1,
+            deposit_paused: false,
+        };
+        let pda = migrate_legacy_pda(&legacy_pda_data(&legacy)).unwrap();
+        let mut data = vec![0u8; 8 + Pda::INIT_SPACE];
+        pda.try_serialize(&mut &mut data[..]).unwrap();
+
+        // Act
+        let result = migrate_legacy_pda(&data);
+
+        // Assert
+        assert_eq!(result.err(), Some(Errors::PdaAlreadyUpgraded.into()));
+    }
+
+    #[test]
+    fn test_migrate_legacy_pda_wrong_discriminator() {
+        // Arrange
+        let mut data = vec![0u8; LEGACY_PDA_SPACE];
+        data[..8].copy_from_slice(&[1u8; 8]);
+
+        // Act
+        let result = migrate_legacy_pda(&data);
+
+        // Assert
+        assert_eq!(result.err(), Some(Errors::InvalidPdaLayout.into()));
+    }
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::errors::Errors;
+use crate::state::WhitelistEntry;
+use crate::utils::{LEGACY_WHITELIST_ENTRY_SPACE, WHITELIST_ENTRY_VERSION};
+
+/// Maps a whitelist entry stored in the unversioned, empty layout to the current layout
+///
+/// # Arguments
+///
+/// * `data` - The account data, including the discriminator
+/// * `decimals` - The decimals of the whitelisted mint
+/// * `token_program` - The token program owning the whitelisted mint
+///
+/// # Returns
+///
+/// * `Result<WhitelistEntry>` - The entry in the current layout, with the settings `whitelist_spl_mint`
+///   gives a new entry
+///
+/// # Errors
+///
+/// Returns `Errors::InvalidWhitelistEntryLayout` if the data is not a whitelist entry and
+/// `Errors::WhitelistEntryAlreadyUpgraded` if it does not use the unversioned layout
+pub fn migrate_legacy_whitelist_entry(
+    data: &[u8],
+    decimals: u8,
+    token_program: Pubkey,
+) -> Result<WhitelistEntry> {
+    require!(
+        data.len() >= 8 && data[..8] == *WhitelistEntry::DISCRIMINATOR,
+        Errors::InvalidWhitelistEntryLayout
+    );
+    require!(
+        data.len() == LEGACY_WHITELIST_ENTRY_SPACE,
+        Errors::WhitelistEntryAlreadyUpgraded
+    );
+
+    Ok(WhitelistEntry {
+        withdraw_only: false,
+        deposit_paused: false,
+        min_deposit: 0,
+        max_deposit: u64::MAX,
+        decimals,
+        token_program,
+        deposit_fee: None,
+        version: WHITELIST_ENTRY_VERSION,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_legacy_whitelist_entry_success() {
+        // Arrange
+        let data = WhitelistEntry::DISCRIMINATOR.to_vec();
+        let token_program = anchor_spl::token::ID;
+
+        // Act
+        let entry = migrate_legacy_whitelist_entry(&data, 6, token_program).unwrap();
+        let mut migrated_data = [0u8; 8 + WhitelistEntry::INIT_SPACE];
+        entry.try_serialize(&mut &mut migrated_data[..]).unwrap();
+        let migrated = WhitelistEntry::try_deserialize(&mut &migrated_data[..]).unwrap();
+
+        // Assert
+        assert!(!migrated.withdraw_only);
+        assert!(!migrated.deposit_paused);
+        assert_eq!(migrated.min_deposit, 0);
+        assert_eq!(migrated.max_deposit, u64::MAX);
+        assert_eq!(migrated.decimals, 6);
+        assert_eq!(migrated.token_program, token_program);
+        assert_eq!(migrated.deposit_fee, None);
+        assert_eq!(migrated.version, WHITELIST_ENTRY_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_legacy_whitelist_entry_already_upgraded() {
+        // Arrange
+        let mut data = vec![0u8; 8 + WhitelistEntry::INIT_SPACE];
+        data[..8].copy_from_slice(WhitelistEntry::DISCRIMINATOR);
+
+        // Act
+        let result = migrate_legacy_whitelist_entry(&data, 6, anchor_spl::token::ID);
+
+        // Assert
+        assert_eq!(
+            result.err(),
+            Some(Errors::WhitelistEntryAlreadyUpgraded.into())
+        );
+    }
+
+    #[test]
+    fn test_migrate_legacy_whitelist_entry_wrong_discriminator() {
+        // Arrange
+        let data = [1u8; LEGACY_WHITELIST_ENTRY_SPACE];
+
+        // Act
+        let result = migrate_legacy_whitelist_entry(&data, 6, anchor_spl::token::ID);
+
+        // Assert
+        assert_eq!(
+            result.err(),
+            Some(Errors::InvalidWhitelistEntryLayout.into())
+        );
+    }
+}
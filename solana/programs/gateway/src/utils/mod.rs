@@ -1,19 +1,63 @@
+pub mod apply_rate_limit;
 pub mod constants;
+pub mod create_pending_withdrawal;
+pub mod create_recipient_ata;
+pub mod forwarded_account_policy;
+pub mod hash_expected_accounts;
+pub mod load_expected_accounts;
+pub mod load_return_data;
+pub mod load_whitelist_entry;
+pub mod migrate_legacy_pda;
+pub mod migrate_legacy_whitelist_entry;
 pub mod prepare_account_metas;
+pub mod promote_pending_tss;
 pub mod recover_and_verify_eth_address;
+pub mod release_rate_limit;
+pub mod resolve_deposit_fee;
+pub mod schedule_tss_update;
 pub mod validate_message;
 pub mod validate_message_hash;
 pub mod verify_and_update_nonce;
 pub mod verify_ata_match;
 pub mod verify_authority;
+pub mod verify_custody_balance;
+pub mod verify_expected_accounts;
+pub mod verify_min_deposit;
+pub mod verify_mint_extensions;
+pub mod verify_multisig_disabled;
 pub mod verify_payload_size;
+pub mod verify_receiver;
+pub mod verify_swap_output;
+pub mod wrap_sol;
 
+pub use apply_rate_limit::*;
 pub use constants::*;
+pub use create_pending_withdrawal::*;
+pub use create_recipient_ata::*;
+pub use forwarded_account_policy::*;
+pub use hash_expected_accounts::*;
+pub use load_expected_accounts::*;
+pub use load_return_data::*;
+pub use load_whitelist_entry::*;
+pub use migrate_legacy_pda::*;
+pub use migrate_legacy_whitelist_entry::*;
 pub use prepare_account_metas::*;
+pub use promote_pending_tss::*;
 pub use recover_and_verify_eth_address::*;
+pub use release_rate_limit::*;
+pub use resolve_deposit_fee::*;
+pub use schedule_tss_update::*;
 pub use validate_message::*;
 pub use validate_message_hash::*;
 pub use verify_and_update_nonce::*;
 pub use verify_ata_match::*;
 pub use verify_authority::*;
+pub use verify_custody_balance::*;
+pub use verify_expected_accounts::*;
+pub use verify_min_deposit::*;
+pub use verify_mint_extensions::*;
+pub use verify_multisig_disabled::*;
 pub use verify_payload_size::*;
+pub use verify_receiver::*;
+pub use verify_swap_output::*;
+pub use wrap_sol::*;
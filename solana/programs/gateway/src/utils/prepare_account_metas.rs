@@ -1,31 +1,35 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::AccountMeta;
 
+use super::forwarded_account_policy;
 use crate::errors::Errors;
-use crate::state::Pda;
+use crate::state::{ForwardedAccountPolicy, Pda};
 
-/// Prepares account metas for withdraw and call, revert if unallowed account is passed
+/// Prepares account metas for withdraw and call, applying the forwarded account policy to each account.
+/// Reverts if an unallowed account is passed, forwards gateway-controlled accounts as read-only,
+/// and forwards the gateway caller PDA as a signer.
 pub fn prepare_account_metas(
     remaining_accounts: &[AccountInfo],
     signer: &Signer,
     pda: &Account<Pda>,
+    caller: &Pubkey,
 ) -> Result<Vec<AccountMeta>> {
     let mut account_metas = Vec::new();
 
     for account_info in remaining_accounts.iter() {
-        let account_key = account_info.key;
+        let (is_signer, is_writable) =
+            match forwarded_account_policy(account_info, signer.key, &pda.key(), caller) {
+                ForwardedAccountPolicy::Deny => return err!(Errors::InvalidInstructionData),
+                ForwardedAccountPolicy::ReadOnly => (false, false),
+                ForwardedAccountPolicy::GatewaySigner => (true, false),
+                ForwardedAccountPolicy::AsRequested => (false, account_info.is_writable),
+            };
 
-        // Prevent signer from being included
-        require!(account_key != signer.key, Errors::InvalidInstructionData);
-
-        // Gateway pda can be added as not writable
-        if *account_key == pda.key() {
-            account_metas.push(AccountMeta::new_readonly(*account_key, false));
-        } else if account_info.is_writable {
-            account_metas.push(AccountMeta::new(*account_key, false));
-        } else {
-            account_metas.push(AccountMeta::new_readonly(*account_key, false));
-        }
+        account_metas.push(AccountMeta {
+            pubkey: *account_info.key,
+            is_signer,
+            is_writable,
+        });
     }
     Ok(account_metas)
 }
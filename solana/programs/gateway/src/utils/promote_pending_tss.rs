@@ -0,0 +1,14 @@
+use crate::state::Pda;
+use anchor_lang::prelude::*;
+
+/// Replaces the TSS address with the pending one once its overlap window has ended.
+pub fn promote_pending_tss(pda: &mut Account<Pda>, slot: u64) {
+    if pda.pending_tss_address != [0u8; 20] && slot >= pda.tss_overlap_end_slot {
+        msg!(
+            "TSS rotation completed: new TSS address = {:?}",
+            pda.pending_tss_address
+        );
+        pda.tss_address = pda.pending_tss_address;
+        pda.pending_tss_address = [0u8; 20];
+    }
+}
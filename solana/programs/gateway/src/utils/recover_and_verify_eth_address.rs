@@ -5,8 +5,10 @@ use anchor_lang::solana_program::secp256k1_recover::secp256k1_recover;
 
 use crate::errors::Errors;
 use crate::state::Pda;
+use crate::utils::promote_pending_tss;
 
 /// Recovers and verifies eth address from signature.
+/// During a scheduled TSS rotation, the pending TSS address is accepted from its activation slot.
 pub fn recover_and_verify_eth_address(
     pda: &mut Account<Pda>,
     message_hash: &[u8; 32],
@@ -24,7 +26,13 @@ pub fn recover_and_verify_eth_address(
     let mut eth_address = [0u8; 20];
     eth_address.copy_from_slice(address);
 
-    if eth_address != pda.tss_address {
+    let slot = Clock::get()?.slot;
+    promote_pending_tss(pda, slot);
+    let is_pending_tss = pda.pending_tss_address != [0u8; 20]
+        && slot >= pda.tss_activation_slot
+        && eth_address == pda.pending_tss_address;
+
+    if eth_address != pda.tss_address && !is_pending_tss {
         msg!("ECDSA signature error");
         return err!(Errors::TSSAuthenticationFailed);
     }
@@ -0,0 +1,71 @@
+use crate::state::RateLimit;
+
+/// Releases the part of the window amount that has decayed since the last update and removes `amount`
+///
+/// # Arguments
+///
+/// * `limit` - The rate limit of the asset
+/// * `amount` - The amount the cancelled outbound consumed from the window
+/// * `slot` - The current slot
+pub fn release_rate_limit(limit: &mut RateLimit, amount: u64, slot: u64) {
+    let elapsed = slot.saturating_sub(limit.last_update_slot) as u128;
+    let released = (limit.max_per_window as u128 * elapsed / limit.window_slots as u128)
+        .min(u64::MAX as u128) as u64;
+
+    limit.window_amount = limit
+        .window_amount
+        .saturating_sub(released)
+        .saturating_sub(amount);
+    limit.last_update_slot = slot;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::consume_rate_limit;
+    use anchor_lang::prelude::Pubkey;
+
+    fn rate_limit() -> RateLimit {
+        RateLimit {
+            mint: Pubkey::default(),
+            max_per_withdrawal: 100,
+            max_per_window: 250,
+            window_slots: 1000,
+            window_amount: 0,
+            last_update_slot: 0,
+            delay_threshold: 0,
+            delay_slots: 0,
+        }
+    }
+
+    #[test]
+    fn test_release_rate_limit_frees_window() {
+        // Arrange
+        let mut limit = rate_limit();
+        consume_rate_limit(&mut limit, 100, 10).unwrap();
+        consume_rate_limit(&mut limit, 100, 10).unwrap();
+
+        // Act
+        release_rate_limit(&mut limit, 100, 10);
+        let result = consume_rate_limit(&mut limit, 100, 10);
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(limit.window_amount, 200);
+    }
+
+    #[test]
+    fn test_release_rate_limit_after_decay() {
+        // Arrange
+        let mut limit = rate_limit();
+        consume_rate_limit(&mut limit, 100, 10).unwrap();
+
+        // Act
+        // 200 slots release 50 of the window before the cancelled amount is removed
+        release_rate_limit(&mut limit, 100, 210);
+
+        // Assert
+        assert_eq!(limit.window_amount, 0);
+        assert_eq!(limit.last_update_slot, 210);
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::get_instruction_relative;
+
+use crate::errors::Errors;
+use crate::state::FeeExemption;
+
+/// Resolves the deposit fee to charge.
+/// The fee is waived when the gateway is invoked through CPI directly by a program holding a fee
+/// exemption. The gateway must run one level below the top-level instruction, so the program of
+/// the top-level instruction is the immediate caller and not a program further up a nested CPI.
+pub fn resolve_deposit_fee(
+    deposit_fee: u64,
+    fee_exemption: Option<&Account<FeeExemption>>,
+    instructions_sysvar: Option<&UncheckedAccount>,
+) -> Result<u64> {
+    let Some(fee_exemption) = fee_exemption else {
+        return Ok(deposit_fee);
+    };
+    let instructions_sysvar = instructions_sysvar.ok_or(Errors::InvalidFeeExemption)?;
+
+    require!(
+        get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT + 1,
+        Errors::InvalidFeeExemption
+    );
+
+    let current_ix = get_instruction_relative(0, &instructions_sysvar.to_account_info())?;
+    require!(
+        current_ix.program_id == fee_exemption.program_id,
+        Errors::InvalidFeeExemption
+    );
+
+    Ok(0)
+}
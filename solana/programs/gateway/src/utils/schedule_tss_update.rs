@@ -0,0 +1,29 @@
+use crate::{errors::Errors, state::Pda, utils::promote_pending_tss};
+use anchor_lang::prelude::*;
+
+/// Schedules a rotation to `tss_address` accepted from `activation_slot`.
+/// Both TSS addresses are accepted until `activation_slot + overlap_slots`, after which the
+/// pending TSS replaces the current one. The nonce is shared and carries over unchanged,
+/// so outbounds signed by the current TSS remain valid during the overlap window.
+/// Scheduling again replaces a rotation that has not completed yet.
+pub fn schedule_tss_update(
+    pda: &mut Account<Pda>,
+    tss_address: [u8; 20],
+    activation_slot: u64,
+    overlap_slots: u64,
+) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    promote_pending_tss(pda, slot);
+
+    require!(tss_address != [0u8; 20], Errors::InvalidTssRotation);
+    require!(activation_slot >= slot, Errors::InvalidTssRotation);
+    let overlap_end_slot = activation_slot
+        .checked_add(overlap_slots)
+        .ok_or(Errors::InvalidTssRotation)?;
+
+    pda.pending_tss_address = tss_address;
+    pda.tss_activation_slot = activation_slot;
+    pda.tss_overlap_end_slot = overlap_end_slot;
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
 
 use crate::errors::Errors;
 
@@ -9,6 +9,7 @@ use crate::errors::Errors;
 ///
 /// * `owner` - The owner of the associated token account
 /// * `mint` - The mint (token) address
+/// * `token_program` - The token program owning the mint, SPL Token or Token-2022
 /// * `actual_ata` - The provided associated token account address to verify
 ///
 /// # Returns
@@ -18,8 +19,13 @@ use crate::errors::Errors;
 /// # Errors
 ///
 /// Returns `Errors::SPLAtaAndMintAddressMismatch` if the provided ATA doesn't match the expected one
-pub fn verify_ata_match(owner: &Pubkey, mint: &Pubkey, actual_ata: &Pubkey) -> Result<()> {
-    let expected_ata = get_associated_token_address(owner, mint);
+pub fn verify_ata_match(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    actual_ata: &Pubkey,
+) -> Result<()> {
+    let expected_ata = get_associated_token_address_with_program_id(owner, mint, token_program);
     require!(
         expected_ata == *actual_ata,
         Errors::SPLAtaAndMintAddressMismatch
@@ -30,16 +36,19 @@ pub fn verify_ata_match(owner: &Pubkey, mint: &Pubkey, actual_ata: &Pubkey) -> R
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_spl::token::ID as TOKEN_PROGRAM_ID;
+    use anchor_spl::token_2022::ID as TOKEN_2022_PROGRAM_ID;
 
     #[test]
     fn test_verify_ata_match_success() {
         // Arrange
         let owner = Pubkey::new_unique();
         let mint = Pubkey::new_unique();
-        let expected_ata = get_associated_token_address(&owner, &mint);
+        let expected_ata =
+            get_associated_token_address_with_program_id(&owner, &mint, &TOKEN_PROGRAM_ID);
 
         // Act
-        let result = verify_ata_match(&owner, &mint, &expected_ata);
+        let result = verify_ata_match(&owner, &mint, &TOKEN_PROGRAM_ID, &expected_ata);
 
         // Assert
         assert!(result.is_ok());
@@ -53,9 +62,29 @@ mod tests {
 
         // Act
         let wrong_ata = Pubkey::new_unique();
-        let result = verify_ata_match(&owner, &mint, &wrong_ata);
+        let result = verify_ata_match(&owner, &mint, &TOKEN_PROGRAM_ID, &wrong_ata);
 
         // Assert
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_verify_ata_match_token_2022() {
+        // Arrange
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let token_ata =
+            get_associated_token_address_with_program_id(&owner, &mint, &TOKEN_PROGRAM_ID);
+        let token_2022_ata =
+            get_associated_token_address_with_program_id(&owner, &mint, &TOKEN_2022_PROGRAM_ID);
+
+        // Act
+        let result = verify_ata_match(&owner, &mint, &TOKEN_2022_PROGRAM_ID, &token_2022_ata);
+        let wrong_program_result =
+            verify_ata_match(&owner, &mint, &TOKEN_2022_PROGRAM_ID, &token_ata);
+
+        // Assert
+        assert!(result.is_ok());
+        assert!(wrong_program_result.is_err());
+    }
 }
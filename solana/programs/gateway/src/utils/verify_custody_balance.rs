@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::Errors;
+use crate::state::Pda;
+
+/// Verifies the gateway PDA can pay out lamports without spending collected fees or its rent
+///
+/// # Arguments
+///
+/// * `pda` - The gateway PDA paying out the lamports
+/// * `amount` - The amount of lamports leaving the PDA
+///
+/// # Errors
+///
+/// Returns `Errors::InsufficientCustodyBalance` if the PDA would be left with less than its
+/// rent-exempt minimum plus the collected fees
+pub fn verify_custody_balance(pda: &Account<Pda>, amount: u64) -> Result<()> {
+    let pda_info = pda.to_account_info();
+    let rent_minimum = Rent::get()?.minimum_balance(pda_info.data_len());
+
+    verify_reserved_lamports(
+        pda_info.lamports(),
+        amount,
+        rent_minimum.saturating_add(pda.collected_fees),
+    )
+}
+
+fn verify_reserved_lamports(lamports: u64, amount: u64, reserved_lamports: u64) -> Result<()> {
+    let remaining = lamports
+        .checked_sub(amount)
+        .ok_or(Errors::InsufficientCustodyBalance)?;
+    require!(
+        remaining >= reserved_lamports,
+        Errors::InsufficientCustodyBalance
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_reserved_lamports_success() {
+        // Arrange
+        let lamports = 10_000;
+        let reserved_lamports = 4_000;
+
+        // Act
+        let at_reserve = verify_reserved_lamports(lamports, 6_000, reserved_lamports);
+        let above_reserve = verify_reserved_lamports(lamports, 1, reserved_lamports);
+
+        // Assert
+        assert!(at_reserve.is_ok());
+        assert!(above_reserve.is_ok());
+    }
+
+    #[test]
+    fn test_verify_reserved_lamports_spends_reserve() {
+        // Arrange
+        let lamports = 10_000;
+        let reserved_lamports = 4_000;
+
+        // Act
+        let into_reserve = verify_reserved_lamports(lamports, 6_001, reserved_lamports);
+        let above_balance = verify_reserved_lamports(lamports, 10_001, 0);
+
+        // Assert
+        assert_eq!(
+            into_reserve.err(),
+            Some(Errors::InsufficientCustodyBalance.into())
+        );
+        assert_eq!(
+            above_balance.err(),
+            Some(Errors::InsufficientCustodyBalance.into())
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+
+use crate::errors::Errors;
+use crate::state::ExpectedAccount;
+
+/// Verifies the accounts forwarded to a destination program against the account list it published
+///
+/// # Arguments
+///
+/// * `account_metas` - The account metas forwarded to the destination program
+/// * `expected_accounts` - The ordered account list published by the destination program
+///
+/// # Returns
+///
+/// * `Result<()>` - Ok if the forwarded accounts match the published list in order and writability
+///
+/// # Errors
+///
+/// Returns `Errors::UnexpectedForwardedAccounts` if the forwarded accounts differ from the published list
+pub fn verify_expected_accounts(
+    account_metas: &[AccountMeta],
+    expected_accounts: &[ExpectedAccount],
+) -> Result<()> {
+    require!(
+        account_metas.len() == expected_accounts.len(),
+        Errors::UnexpectedForwardedAccounts
+    );
+
+    for (account_meta, expected_account) in account_metas.iter().zip(expected_accounts) {
+        require!(
+            account_meta.pubkey == expected_account.pubkey
+                && account_meta.is_writable == expected_account.is_writable,
+            Errors::UnexpectedForwardedAccounts
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_expected_accounts_match() {
+        // Arrange
+        let writable = Pubkey::new_unique();
+        let readonly = Pubkey::new_unique();
+        let account_metas = vec![
+            AccountMeta::new(writable, false),
+            AccountMeta::new_readonly(readonly, false),
+        ];
+        let expected_accounts = vec![
+            ExpectedAccount {
+                pubkey: writable,
+                is_writable: true,
+            },
+            ExpectedAccount {
+                pubkey: readonly,
+                is_writable: false,
+            },
+        ];
+
+        // Act
+        let result = verify_expected_accounts(&account_metas, &expected_accounts);
+
+        // Assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_verify_expected_accounts_mismatch() {
+        // Arrange
+        let account = Pubkey::new_unique();
+        let expected_accounts = vec![ExpectedAccount {
+            pubkey: account,
+            is_writable: false,
+        }];
+
+        // Act
+        let writable_result =
+            verify_expected_accounts(&[AccountMeta::new(account, false)], &expected_accounts);
+        let substituted_result = verify_expected_accounts(
+            &[AccountMeta::new_readonly(Pubkey::new_unique(), false)],
+            &expected_accounts,
+        );
+        let missing_result = verify_expected_accounts(&[], &expected_accounts);
+
+        // Assert
+        assert_eq!(
+            writable_result.err(),
+            Some(Errors::UnexpectedForwardedAccounts.into())
+        );
+        assert_eq!(
+            substituted_result.err(),
+            Some(Errors::UnexpectedForwardedAccounts.into())
+        );
+        assert_eq!(
+            missing_result.err(),
+            Some(Errors::UnexpectedForwardedAccounts.into())
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::Errors;
+
+/// Verify a deposit amount meets the minimum deposit of the asset
+///
+/// # Arguments
+///
+/// * `amount` - The deposited amount, in lamports or token base units
+/// * `min_deposit` - The configured minimum deposit of the asset, zero if not configured
+///
+/// # Returns
+///
+/// * `Result<()>` - Ok if the amount is non-zero and not below the minimum deposit
+///
+/// # Errors
+///
+/// Returns `Errors::DepositBelowMinimum` and logs the effective minimum if the amount is below it
+pub fn verify_min_deposit(amount: u64, min_deposit: u64) -> Result<()> {
+    // Zero deposits only cost observer work, so the effective minimum is at least 1
+    let min_deposit = min_deposit.max(1);
+    if amount < min_deposit {
+        msg!(
+            "Deposit amount {} is below the minimum deposit of {}",
+            amount,
+            min_deposit
+        );
+        return err!(Errors::DepositBelowMinimum);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_min_deposit_success() {
+        // Arrange
+        let min_deposit = 1_000;
+
+        // Act
+        let at_minimum = verify_min_deposit(1_000, min_deposit);
+        let without_minimum = verify_min_deposit(1, 0);
+
+        // Assert
+        assert!(at_minimum.is_ok());
+        assert!(without_minimum.is_ok());
+    }
+
+    #[test]
+    fn test_verify_min_deposit_below_minimum() {
+        // Arrange
+        let min_deposit = 1_000;
+
+        // Act
+        let below_minimum = verify_min_deposit(999, min_deposit);
+        let zero_amount = verify_min_deposit(0, 0);
+
+        // Assert
+        assert_eq!(
+            below_minimum.err(),
+            Some(Errors::DepositBelowMinimum.into())
+        );
+        assert_eq!(zero_amount.err(), Some(Errors::DepositBelowMinimum.into()));
+    }
+}
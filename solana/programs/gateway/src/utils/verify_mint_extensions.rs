@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, StateWithExtensions},
+};
+
+use crate::errors::Errors;
+use crate::utils::UNSUPPORTED_MINT_EXTENSIONS;
+
+/// Verify a mint has no extension the gateway does not support
+///
+/// # Arguments
+///
+/// * `mint` - The mint account, owned by SPL Token or Token-2022
+///
+/// # Returns
+///
+/// * `Result<()>` - Ok if the mint is a legacy SPL mint or has only supported extensions
+///
+/// # Errors
+///
+/// Returns `Errors::UnsupportedMintExtension` if the mint has an unsupported extension
+pub fn verify_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)
+        .map_err(|_| error!(Errors::UnsupportedMintExtension))?;
+    let extensions = state
+        .get_extension_types()
+        .map_err(|_| error!(Errors::UnsupportedMintExtension))?;
+
+    require!(
+        !extensions
+            .iter()
+            .any(|extension| UNSUPPORTED_MINT_EXTENSIONS.contains(extension)),
+        Errors::UnsupportedMintExtension
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        mint_close_authority::MintCloseAuthority, non_transferable::NonTransferable,
+        permanent_delegate::PermanentDelegate, BaseStateWithExtensionsMut, ExtensionType,
+        StateWithExtensionsMut,
+    };
+    use anchor_spl::token_2022::spl_token_2022::state::Mint;
+
+    fn mint_data(extensions: &[ExtensionType]) -> Vec<u8> {
+        let len = ExtensionType::try_calculate_account_len::<Mint>(extensions).unwrap();
+        let mut data = vec![0u8; len];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        for extension in extensions {
+            match extension {
+                ExtensionType::NonTransferable => {
+                    state.init_extension::<NonTransferable>(true).unwrap();
+                }
+                ExtensionType::PermanentDelegate => {
+                    state.init_extension::<PermanentDelegate>(true).unwrap();
+                }
+                ExtensionType::MintCloseAuthority => {
+                    state.init_extension::<MintCloseAuthority>(true).unwrap();
+                }
+                _ => unreachable!(),
+            }
+        }
+        state.base.is_initialized = true;
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    fn verify(owner: &Pubkey, data: &mut [u8]) -> Result<()> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mint = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+        verify_mint_extensions(&mint)
+    }
+
+    #[test]
+    fn test_verify_mint_extensions_supported() {
+        // Arrange
+        let mut plain = mint_data(&[]);
+        let mut close_authority = mint_data(&[ExtensionType::MintCloseAuthority]);
+
+        // Act
+        let plain_result = verify(&spl_token_2022::ID, &mut plain);
+        let close_authority_result = verify(&spl_token_2022::ID, &mut close_authority);
+
+        // Assert
+        assert!(plain_result.is_ok());
+        assert!(close_authority_result.is_ok());
+    }
+
+    #[test]
+    fn test_verify_mint_extensions_unsupported() {
+        // Arrange
+        let mut non_transferable = mint_data(&[ExtensionType::NonTransferable]);
+        let mut permanent_delegate = mint_data(&[
+            ExtensionType::MintCloseAuthority,
+            ExtensionType::PermanentDelegate,
+        ]);
+
+        // Act
+        let non_transferable_result = verify(&spl_token_2022::ID, &mut non_transferable);
+        let permanent_delegate_result = verify(&spl_token_2022::ID, &mut permanent_delegate);
+
+        // Assert
+        assert_eq!(
+            non_transferable_result.err(),
+            Some(Errors::UnsupportedMintExtension.into())
+        );
+        assert_eq!(
+            permanent_delegate_result.err(),
+            Some(Errors::UnsupportedMintExtension.into())
+        );
+    }
+
+    #[test]
+    fn test_verify_mint_extensions_legacy_token_program() {
+        // Arrange
+        let mut data = vec![0u8; 82];
+
+        // Act
+        let result = verify(&anchor_spl::token::ID, &mut data);
+
+        // Assert
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,8 @@
+use crate::errors::Errors;
+use anchor_lang::prelude::*;
+/// Verifies that no admin config is initialized
+/// Returns an error if the operation must go through admin proposals
+pub fn verify_multisig_disabled(admin_config: &AccountInfo) -> Result<()> {
+    require!(admin_config.data_is_empty(), Errors::MultisigRequired);
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use super::constants::{MAX_RECEIVER_ADDRESS_SIZE, MIN_BITCOIN_ADDRESS_SIZE};
+use crate::errors::Errors;
+use crate::state::{AddressType, Receiver};
+
+/// Verify the receiver of a versioned deposit or call
+///
+/// # Arguments
+///
+/// * `receiver` - The receiver, including its address type and target chain ID
+///
+/// # Returns
+///
+/// * `Result<()>` - Ok if the address is valid for its address type, Error otherwise
+///
+/// # Errors
+///
+/// Returns `Errors::EmptyReceiver` if the address is empty or all zeros, and
+/// `Errors::InvalidReceiver` if the chain ID is zero or the address does not match its address type
+pub fn verify_receiver(receiver: &Receiver) -> Result<()> {
+    let address = &receiver.address;
+    require!(address.iter().any(|byte| *byte != 0), Errors::EmptyReceiver);
+    require!(receiver.chain_id != 0, Errors::InvalidReceiver);
+
+    let valid = match receiver.address_type {
+        AddressType::Evm => address.len() == 20,
+        AddressType::Solana => address.len() == 32,
+        AddressType::Bitcoin => {
+            (MIN_BITCOIN_ADDRESS_SIZE..=MAX_RECEIVER_ADDRESS_SIZE).contains(&address.len())
+                && address.iter().all(u8::is_ascii_alphanumeric)
+        }
+    };
+    require!(valid, Errors::InvalidReceiver);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receiver(address_type: AddressType, address: &[u8]) -> Receiver {
+        Receiver {
+            address_type,
+            address: address.to_vec(),
+            chain_id: 7000,
+        }
+    }
+
+    #[test]
+    fn test_verify_receiver_success() {
+        // Arrange
+        let evm = receiver(AddressType::Evm, &[1u8; 20]);
+        let solana = receiver(AddressType::Solana, Pubkey::new_unique().as_ref());
+        let bitcoin = receiver(
+            AddressType::Bitcoin,
+            b"bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+        );
+
+        // Act & Assert
+        assert!(verify_receiver(&evm).is_ok());
+        assert!(verify_receiver(&solana).is_ok());
+        assert!(verify_receiver(&bitcoin).is_ok());
+    }
+
+    #[test]
+    fn test_verify_receiver_wrong_length() {
+        // Arrange
+        let evm = receiver(AddressType::Evm, &[1u8; 32]);
+        let solana = receiver(AddressType::Solana, &[1u8; 20]);
+        let bitcoin = receiver(AddressType::Bitcoin, b"bc1qshort");
+
+        // Act & Assert
+        assert_eq!(
+            verify_receiver(&evm).err(),
+            Some(Errors::InvalidReceiver.into())
+        );
+        assert_eq!(
+            verify_receiver(&solana).err(),
+            Some(Errors::InvalidReceiver.into())
+        );
+        assert_eq!(
+            verify_receiver(&bitcoin).err(),
+            Some(Errors::InvalidReceiver.into())
+        );
+    }
+
+    #[test]
+    fn test_verify_receiver_invalid_bitcoin_characters() {
+        // Arrange
+        let bitcoin = receiver(
+            AddressType::Bitcoin,
+            b"bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5md!",
+        );
+
+        // Act
+        let result = verify_receiver(&bitcoin);
+
+        // Assert
+        assert_eq!(result.err(), Some(Errors::InvalidReceiver.into()));
+    }
+
+    #[test]
+    fn test_verify_receiver_empty_or_zero_chain() {
+        // Arrange
+        let empty = receiver(AddressType::Evm, &[0u8; 20]);
+        let mut zero_chain = receiver(AddressType::Evm, &[1u8; 20]);
+        zero_chain.chain_id = 0;
+
+        // Act & Assert
+        assert_eq!(
+            verify_receiver(&empty).err(),
+            Some(Errors::EmptyReceiver.into())
+        );
+        assert_eq!(
+            verify_receiver(&zero_chain).err(),
+            Some(Errors::InvalidReceiver.into())
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::Errors;
+
+/// Verify a swap performed before a deposit stayed within its input and slippage bounds
+///
+/// # Arguments
+///
+/// * `amount_spent` - The balance decrease of the signer's source token account
+/// * `amount_in` - The maximum input amount the signer agreed to swap
+/// * `amount_received` - The balance increase of the gateway ATA of the output mint
+/// * `minimum_amount_out` - The minimum output amount the signer accepts
+///
+/// # Returns
+///
+/// * `Result<u64>` - The output amount received by the gateway, to be credited as the deposit
+///
+/// # Errors
+///
+/// Returns `Errors::SwapInputExceeded` if the swap spent more than `amount_in` and
+/// `Errors::SlippageExceeded` if the gateway received less than `minimum_amount_out`
+pub fn verify_swap_output(
+    amount_spent: u64,
+    amount_in: u64,
+    amount_received: u64,
+    minimum_amount_out: u64,
+) -> Result<u64> {
+    require!(amount_spent <= amount_in, Errors::SwapInputExceeded);
+    require!(
+        amount_received >= minimum_amount_out,
+        Errors::SlippageExceeded
+    );
+
+    Ok(amount_received)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_swap_output_success() {
+        // Act
+        let result = verify_swap_output(1_000, 1_000, 1_950, 1_900);
+
+        // Assert
+        assert_eq!(result.unwrap(), 1_950);
+    }
+
+    #[test]
+    fn test_verify_swap_output_slippage_exceeded() {
+        // Act
+        let result = verify_swap_output(1_000, 1_000, 1_850, 1_900);
+
+        // Assert
+        assert_eq!(result.err(), Some(Errors::SlippageExceeded.into()));
+    }
+
+    #[test]
+    fn test_verify_swap_output_input_exceeded() {
+        // Act
+        let result = verify_swap_output(1_001, 1_000, 1_950, 1_900);
+
+        // Assert
+        assert_eq!(result.err(), Some(Errors::SwapInputExceeded.into()));
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{sync_native, SyncNative};
+
+/// Moves `amount` lamports from the gateway PDA into a wrapped SOL token account and syncs its token balance
+///
+/// # Arguments
+///
+/// * `pda` - The gateway PDA holding the SOL
+/// * `wsol_account` - The wrapped SOL token account receiving the SOL
+/// * `token_program` - The SPL Token program
+/// * `amount` - The amount of lamports to wrap
+pub fn wrap_sol<'info>(
+    pda: &AccountInfo<'info>,
+    wsol_account: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    pda.sub_lamports(amount)?;
+    wsol_account.add_lamports(amount)?;
+
+    sync_native(CpiContext::new(
+        token_program.clone(),
+        SyncNative {
+            account: wsol_account.clone(),
+        },
+    ))
+}
@@ -0,0 +1,309 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{system_program, Discriminator};
+
+use crate::universal_nft_core::*;
+use crate::universal_nft_core::ErrorCode;
+
+/// Migrate the program state from the unversioned layout (only owner)
+pub fn _migrate_program_state(ctx: Context<MigrateProgramState>) -> Result<()> {
+    let state_info = ctx.accounts.state.to_account_info();
+    let legacy: ProgramStateV0 =
+        read_legacy_account::<ProgramState, _>(&state_info, 8 + ProgramStateV0::INIT_SPACE)?;
+    require!(legacy.authority == ctx.accounts.authority.key(), ErrorCode::Unauthorized);
+
+    let state = ProgramState {
+        authority: legacy.authority,
+        name: legacy.name,
+        symbol: legacy.symbol,
+        gateway: legacy.gateway,
+        gas_limit: legacy.gas_limit,
+        uniswap_router: legacy.uniswap_router,
+        is_paused: legacy.is_paused,
+        next_token_id: legacy.next_token_id,
+        total_supply: legacy.total_supply,
+        is_initialized: legacy.is_initialized,
+        version: PROGRAM_STATE_VERSION,
+    };
+    write_migrated_account(
+        &state_info,
+        &state,
+        8 + ProgramState::INIT_SPACE,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit_cpi!(AccountMigrated {
+        version: EVENT_SCHEMA_VERSION,
+        account: state_info.key(),
+        account_version: PROGRAM_STATE_VERSION,
+    });
+
+    Ok(())
+}
+
+/// Migrate a chain connection from the unversioned layout (only owner)
+pub fn _migrate_chain_connection(ctx: Context<MigrateChainConnection>, _chain_id: u64) -> Result<()> {
+    let connection_info = ctx.accounts.connection.to_account_info();
+    let legacy: ChainConnectionV0 = read_legacy_account::<ChainConnection, _>(
+        &connection_info,
+        8 + ChainConnectionV0::INIT_SPACE,
+    )?;
+
+    let connection = ChainConnection {
+        chain_id: legacy.chain_id,
+        contract_address: legacy.contract_address,
+        is_active: legacy.is_active,
+        version: CHAIN_CONNECTION_VERSION,
+    };
+    write_migrated_account(
+        &connection_info,
+        &connection,
+        8 + ChainConnection::INIT_SPACE,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit_cpi!(AccountMigrated {
+        version: EVENT_SCHEMA_VERSION,
+        account: connection_info.key(),
+        account_version: CHAIN_CONNECTION_VERSION,
+    });
+
+    Ok(())
+}
+
+/// Migrate NFT data from the unversioned layout (only owner)
+pub fn _migrate_nft_data(ctx: Context<MigrateNftData>, _token_id: u64) -> Result<()> {
+    let nft_data_info = ctx.accounts.nft_data.to_account_info();
+    let legacy: NftDataV0 =
+        read_legacy_account::<NftData, _>(&nft_data_info, 8 + NftDataV0::INIT_SPACE)?;
+
+    let nft_data = NftData {
+        token_id: legacy.token_id,
+        uri: legacy.uri,
+        owner: legacy.owner,
+        is_burned: legacy.is_burned,
+        original_chain: legacy.original_chain,
+        pending_transfer: legacy.pending_transfer,
+        mint_timestamp: legacy.mint_timestamp,
+        attributes: Vec::new(),
+        version: NFT_DATA_VERSION,
+    };
+    write_migrated_account(
+        &nft_data_info,
+        &nft_data,
+        8 + NftData::INIT_SPACE,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit_cpi!(AccountMigrated {
+        version: EVENT_SCHEMA_VERSION,
+        account: nft_data_info.key(),
+        account_version: NFT_DATA_VERSION,
+    });
+
+    Ok(())
+}
+
+/// Deserialize an account still using its legacy layout.
+/// Legacy accounts share the discriminator of the current type and are recognised by their size.
+fn read_legacy_account<T: Discriminator, L: AnchorDeserialize>(
+    account: &AccountInfo,
+    legacy_len: usize,
+) -> Result<L> {
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == *T::DISCRIMINATOR,
+        ErrorCode::InvalidState
+    );
+    require!(data.len() == legacy_len, ErrorCode::AccountAlreadyMigrated);
+
+    L::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::InvalidState))
+}
+
+/// Realloc an account to its current layout, topping up rent from `payer`, and write it back.
+fn write_migrated_account<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    migrated: &T,
+    new_len: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required_lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+        system_program::transfer(cpi_ctx, shortfall)?;
+    }
+
+    account.realloc(new_len, true)?;
+    let mut data = account.try_borrow_mut_data()?;
+    migrated.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+// Account structs
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateProgramState<'info> {
+    /// CHECK: Legacy layout is validated and deserialized during migration
+    #[account(mut, owner = crate::ID)]
+    pub state: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct MigrateChainConnection<'info> {
+    #[account(
+        has_one = authority,
+        constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
+    pub state: Account<'info, ProgramState>,
+    /// CHECK: Legacy layout is validated and deserialized during migration
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"connection", chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub connection: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(token_id: u64)]
+pub struct MigrateNftData<'info> {
+    #[account(
+        has_one = authority,
+        constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
+    pub state: Account<'info, ProgramState>,
+    /// CHECK: Legacy layout is validated and deserialized during migration
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"nft", token_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub nft_data: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Legacy (unversioned) account layouts, as deployed before versioning
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct ProgramStateV0 {
+    pub authority: Pubkey,
+    #[max_len(50)]
+    pub name: String,
+    #[max_len(10)]
+    pub symbol: String,
+    pub gateway: Pubkey,
+    pub gas_limit: u64,
+    pub uniswap_router: Pubkey,
+    pub is_paused: bool,
+    pub next_token_id: u64,
+    pub total_supply: u64,
+    pub is_initialized: bool,
+}
+
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct ChainConnectionV0 {
+    pub chain_id: u64,
+    #[max_len(200)]
+    pub contract_address: String,
+    pub is_active: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct NftDataV0 {
+    pub token_id: u64,
+    #[max_len(500)]
+    pub uri: String,
+    pub owner: Pubkey,
+    pub is_burned: bool,
+    pub original_chain: Option<u64>,
+    pub pending_transfer: Option<PendingTransfer>,
+    pub mint_timestamp: i64,
+}
+
+// Events
+#[event]
+pub struct AccountMigrated {
+    pub version: u8,
+    pub account: Pubkey,
+    pub account_version: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_legacy_nft_data_from_baseline_layout() {
+        // Arrange
+        let legacy = NftDataV0 {
+            token_id: 42,
+            uri: "https://example.com/42".to_string(),
+            owner: Pubkey::new_unique(),
+            is_burned: false,
+            original_chain: Some(7001),
+            pending_transfer: None,
+            mint_timestamp: 1_700_000_000,
+        };
+        let mut data = NftData::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        // Size of an `NftData` account created before versioning
+        assert_eq!(8 + NftDataV0::INIT_SPACE, 791);
+        data.resize(8 + NftDataV0::INIT_SPACE, 0);
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+
+        // Act
+        let read: NftDataV0 =
+            read_legacy_account::<NftData, _>(&account, 8 + NftDataV0::INIT_SPACE).unwrap();
+
+        // Assert
+        assert_eq!(read.token_id, legacy.token_id);
+        assert_eq!(read.uri, legacy.uri);
+        assert_eq!(read.owner, legacy.owner);
+        assert_eq!(read.original_chain, legacy.original_chain);
+        assert_eq!(read.mint_timestamp, legacy.mint_timestamp);
+    }
+
+    #[test]
+    fn test_read_legacy_nft_data_already_migrated() {
+        // Arrange
+        let mut data = vec![0u8; 8 + NftData::INIT_SPACE];
+        data[..8].copy_from_slice(NftData::DISCRIMINATOR);
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+
+        // Act
+        let result =
+            read_legacy_account::<NftData, NftDataV0>(&account, 8 + NftDataV0::INIT_SPACE);
+
+        // Assert
+        assert_eq!(result.err(), Some(ErrorCode::AccountAlreadyMigrated.into()));
+    }
+}
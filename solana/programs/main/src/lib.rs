@@ -33,6 +33,14 @@ pub mod main {
         universal_nft_core::_set_connected(ctx, chain_id, contract_address)
     }
 
+    pub fn remove_connected(ctx: Context<RemoveConnected>, chain_id: u64) -> Result<()> {
+        universal_nft_core::_remove_connected(ctx, chain_id)
+    }
+
+    pub fn set_authority(ctx: Context<AdminOperation>, new_authority: Pubkey) -> Result<()> {
+        universal_nft_core::_set_authority(ctx, new_authority)
+    }
+
     pub fn pause(ctx: Context<AdminOperation>) -> Result<()> {
         universal_nft_core::_pause(ctx)
     }
@@ -41,8 +49,12 @@ pub mod main {
         universal_nft_core::_unpause(ctx)
     }
 
-    pub fn safe_mint(ctx: Context<SafeMint>, uri: String) -> Result<()> {
-        universal_nft_core::_safe_mint(ctx, uri)
+    pub fn safe_mint(
+        ctx: Context<SafeMint>,
+        uri: String,
+        attributes: Vec<NftAttribute>,
+    ) -> Result<()> {
+        universal_nft_core::_safe_mint(ctx, uri, attributes)
     }
 
     pub fn burn_token(ctx: Context<BurnToken>, token_id: u64) -> Result<()> {
@@ -62,10 +74,11 @@ pub mod main {
         ctx: Context<HandleCrossChainReceive>,
         token_id: u64,
         uri: String,
+        attributes: Vec<NftAttribute>,
         sender_chain_id: u64,
         original_sender: String,
     ) -> Result<()> {
-        universal_nft_core::_handle_cross_chain_receive(ctx, token_id, uri, sender_chain_id, original_sender)
+        universal_nft_core::_handle_cross_chain_receive(ctx, token_id, uri, attributes, sender_chain_id, original_sender)
     }
 
     pub fn revert_transfer(
@@ -91,10 +104,27 @@ pub mod main {
     pub fn is_paused(ctx: Context<TokenQuery>) -> Result<bool> {
         universal_nft_core::_is_paused(ctx)
     }
+
+    pub fn migrate_program_state(ctx: Context<MigrateProgramState>) -> Result<()> {
+        account_migration::_migrate_program_state(ctx)
+    }
+
+    pub fn migrate_chain_connection(
+        ctx: Context<MigrateChainConnection>,
+        chain_id: u64,
+    ) -> Result<()> {
+        account_migration::_migrate_chain_connection(ctx, chain_id)
+    }
+
+    pub fn migrate_nft_data(ctx: Context<MigrateNftData>, token_id: u64) -> Result<()> {
+        account_migration::_migrate_nft_data(ctx, token_id)
+    }
 }
 
 // Universal NFT modules
+pub mod account_migration;
 pub mod universal_nft_core;
 
 // Re-export the account structs and other types that clients need
+pub use account_migration::*;
 pub use universal_nft_core::*;
\ No newline at end of file
@@ -31,8 +31,10 @@ pub fn _initialize(
     state.next_token_id = 1;
     state.total_supply = 0;
     state.is_initialized = true;
+    state.version = PROGRAM_STATE_VERSION;
     
-    emit!(ProgramInitialized {
+    emit_cpi!(ProgramInitialized {
+        version: EVENT_SCHEMA_VERSION,
         authority: state.authority,
         name,
         symbol,
@@ -49,7 +51,8 @@ pub fn _set_gateway(ctx: Context<AdminOperation>, gateway_address: Pubkey) -> Re
     
     state.gateway = gateway_address;
     
-    emit!(GatewayUpdated {
+    emit_cpi!(GatewayUpdated {
+        version: EVENT_SCHEMA_VERSION,
         new_gateway: gateway_address,
     });
     
@@ -64,7 +67,8 @@ pub fn _set_gas_limit(ctx: Context<AdminOperation>, gas_limit: u64) -> Result<()
     
     state.gas_limit = gas_limit;
     
-    emit!(GasLimitUpdated {
+    emit_cpi!(GasLimitUpdated {
+        version: EVENT_SCHEMA_VERSION,
         new_gas_limit: gas_limit,
     });
     
@@ -85,8 +89,10 @@ pub fn _set_connected(
     connection.chain_id = chain_id;
     connection.contract_address = contract_address.clone();
     connection.is_active = true;
+    connection.version = CHAIN_CONNECTION_VERSION;
     
-    emit!(ChainConnected {
+    emit_cpi!(ChainConnected {
+        version: EVENT_SCHEMA_VERSION,
         chain_id,
         contract_address,
     });
@@ -94,6 +100,36 @@ pub fn _set_connected(
     Ok(())
 }
 
+/// Disconnect a chain and close its connection account (only owner)
+pub fn _remove_connected(ctx: Context<RemoveConnected>, chain_id: u64) -> Result<()> {
+    let state = &ctx.accounts.state;
+    require!(!state.is_paused, ErrorCode::ContractPaused);
+
+    emit_cpi!(ChainDisconnected {
+        version: EVENT_SCHEMA_VERSION,
+        chain_id,
+        contract_address: ctx.accounts.connection.contract_address.clone(),
+    });
+
+    Ok(())
+}
+
+/// Transfer program ownership to a new authority (only owner)
+pub fn _set_authority(ctx: Context<AdminOperation>, new_authority: Pubkey) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let previous_authority = state.authority;
+
+    state.authority = new_authority;
+
+    emit_cpi!(AuthorityUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        previous_authority,
+        new_authority,
+    });
+
+    Ok(())
+}
+
 /// Pause the contract (only owner)
 pub fn _pause(ctx: Context<AdminOperation>) -> Result<()> {
     let state = &mut ctx.accounts.state;
@@ -101,7 +137,8 @@ pub fn _pause(ctx: Context<AdminOperation>) -> Result<()> {
     
     state.is_paused = true;
     
-    emit!(ContractPaused {
+    emit_cpi!(ContractPaused {
+        version: EVENT_SCHEMA_VERSION,
         by: ctx.accounts.authority.key(),
     });
     
@@ -115,7 +152,8 @@ pub fn _unpause(ctx: Context<AdminOperation>) -> Result<()> {
     
     state.is_paused = false;
     
-    emit!(ContractUnpaused {
+    emit_cpi!(ContractUnpaused {
+        version: EVENT_SCHEMA_VERSION,
         by: ctx.accounts.authority.key(),
     });
     
@@ -126,9 +164,11 @@ pub fn _unpause(ctx: Context<AdminOperation>) -> Result<()> {
 pub fn _safe_mint(
     ctx: Context<SafeMint>,
     uri: String,
+    attributes: Vec<NftAttribute>,
 ) -> Result<()> {
     let state = &mut ctx.accounts.state;
     require!(!state.is_paused, ErrorCode::ContractPaused);
+    validate_attributes(&attributes)?;
 
     // Get current block number (slot) and clock
     let clock = Clock::get()?;
@@ -147,7 +187,7 @@ pub fn _safe_mint(
     
     // Combine the values: (mint_u64 >> 16) + (block_number << 32) + next_token_id
     let token_id = ((mint_u64 >> 16) & 0x000000FFFFFFFFFF) + 
-                   ((block_number as u64) << 32) + 
+                   (block_number << 32) + 
                    state.next_token_id;
 
     // Increment counters
@@ -161,6 +201,8 @@ pub fn _safe_mint(
     nft_data.owner = ctx.accounts.recipient.key();
     nft_data.is_burned = false;
     nft_data.mint_timestamp = clock.unix_timestamp;
+    nft_data.attributes = attributes;
+    nft_data.version = NFT_DATA_VERSION;
 
     // Update enumerable data
     let enumerable = &mut ctx.accounts.enumerable_data;
@@ -233,7 +275,8 @@ pub fn _safe_mint(
     nft_origin.block_number = block_number;
     nft_origin.mint_timestamp = clock.unix_timestamp;
 
-    emit!(TokenMinted {
+    emit_cpi!(TokenMinted {
+        version: EVENT_SCHEMA_VERSION,
         recipient: ctx.accounts.recipient.key(),
         token_id,
         uri,
@@ -265,9 +308,11 @@ pub fn _burn_token(ctx: Context<BurnToken>, token_id: u64) -> Result<()> {
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::burn(cpi_ctx, 1)?;
 
-    emit!(TokenBurned {
+    emit_cpi!(TokenBurned {
+        version: EVENT_SCHEMA_VERSION,
         owner: ctx.accounts.authority.key(),
         token_id,
+        uri: nft_data.uri.clone(),
     });
 
     Ok(())
@@ -317,11 +362,15 @@ pub fn _transfer_cross_chain(
     token::burn(cpi_ctx, 1)?;
 
     // Emit event with the token ID fetched from nft_origin PDA
-    emit!(TokenTransfer {
+    emit_cpi!(TokenTransfer {
+        version: EVENT_SCHEMA_VERSION,
+        sender: ctx.accounts.owner.key(),
+        mint: ctx.accounts.mint.key(),
         receiver: receiver.clone(),
         destination: destination_chain_id,
         token_id: origin_token_id, // Use token ID from nft_origin PDA
         uri: nft_data.uri.clone(),
+        attributes: nft_data.attributes.clone(),
     });
 
     // Here you would integrate with cross-chain infrastructure
@@ -338,11 +387,13 @@ pub fn _handle_cross_chain_receive(
     ctx: Context<HandleCrossChainReceive>,
     token_id: u64,
     uri: String,
+    attributes: Vec<NftAttribute>,
     sender_chain_id: u64,
     _original_sender: String,
 ) -> Result<()> {
     let state = &mut ctx.accounts.state;
     require!(!state.is_paused, ErrorCode::ContractPaused);
+    validate_attributes(&attributes)?;
     
     state.total_supply += 1;
     
@@ -353,6 +404,8 @@ pub fn _handle_cross_chain_receive(
     nft_data.is_burned = false;
     nft_data.original_chain = Some(sender_chain_id);
     nft_data.mint_timestamp = Clock::get()?.unix_timestamp;
+    nft_data.attributes = attributes.clone();
+    nft_data.version = NFT_DATA_VERSION;
 
     // Create enumerable entry
     let enumerable = &mut ctx.accounts.enumerable_data;
@@ -370,10 +423,12 @@ pub fn _handle_cross_chain_receive(
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::mint_to(cpi_ctx, 1)?;
 
-    emit!(TokenTransferReceived {
+    emit_cpi!(TokenTransferReceived {
+        version: EVENT_SCHEMA_VERSION,
         recipient: ctx.accounts.recipient.key(),
         token_id,
         uri,
+        attributes,
     });
 
     Ok(())
@@ -405,7 +460,8 @@ pub fn _revert_transfer(
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::mint_to(cpi_ctx, 1)?;
 
-    emit!(TokenTransferReverted {
+    emit_cpi!(TokenTransferReverted {
+        version: EVENT_SCHEMA_VERSION,
         owner: original_owner,
         token_id,
         uri: nft_data.uri.clone(),
@@ -414,6 +470,17 @@ pub fn _revert_transfer(
     Ok(())
 }
 
+/// Check attributes fit within the bounds reserved in `NftData`
+fn validate_attributes(attributes: &[NftAttribute]) -> Result<()> {
+    require!(attributes.len() <= MAX_ATTRIBUTES, ErrorCode::TooManyAttributes);
+    for attribute in attributes {
+        require!(!attribute.key.is_empty(), ErrorCode::InvalidAttribute);
+        require!(attribute.key.len() <= MAX_ATTRIBUTE_KEY_LEN, ErrorCode::InvalidAttribute);
+        require!(attribute.value.len() <= MAX_ATTRIBUTE_VALUE_LEN, ErrorCode::InvalidAttribute);
+    }
+    Ok(())
+}
+
 /// Get token by owner and index (for enumeration)
 pub fn _token_of_owner_by_index(
     ctx: Context<TokenQuery>,
@@ -436,6 +503,7 @@ pub fn _is_paused(ctx: Context<TokenQuery>) -> Result<bool> {
 }
 
 // Account structs
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(init, payer = initial_owner, space = 8 + ProgramState::INIT_SPACE)]
@@ -445,17 +513,26 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct AdminOperation<'info> {
-    #[account(mut, has_one = authority)]
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
     pub state: Account<'info, ProgramState>,
     pub authority: Signer<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(chain_id: u64)]
 pub struct SetConnected<'info> {
-    #[account(has_one = authority)]
+    #[account(
+        has_one = authority,
+        constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
     pub state: Account<'info, ProgramState>,
     #[account(
         init,
@@ -470,10 +547,36 @@ pub struct SetConnected<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct RemoveConnected<'info> {
+    #[account(
+        has_one = authority,
+        constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
+    pub state: Account<'info, ProgramState>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"connection", chain_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = connection.version == CHAIN_CONNECTION_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
+    pub connection: Account<'info, ChainConnection>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(uri: String)]
 pub struct SafeMint<'info> {
-    #[account(mut, has_one = authority)]
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
     pub state: Account<'info, ProgramState>,
     #[account(
         init,
@@ -521,12 +624,21 @@ pub struct SafeMint<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(token_id: u64)]
 pub struct BurnToken<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
     pub state: Account<'info, ProgramState>,
-    #[account(mut, seeds = [b"nft", token_id.to_le_bytes().as_ref()], bump)]
+    #[account(
+        mut,
+        seeds = [b"nft", token_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = nft_data.version == NFT_DATA_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
     pub nft_data: Account<'info, NftData>,
     #[account(mut)]
     pub mint: Account<'info, Mint>,
@@ -536,12 +648,21 @@ pub struct BurnToken<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(token_id: u64)]
 pub struct TransferCrossChain<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
     pub state: Account<'info, ProgramState>,
-    #[account(mut, seeds = [b"nft", token_id.to_le_bytes().as_ref()], bump)]
+    #[account(
+        mut,
+        seeds = [b"nft", token_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = nft_data.version == NFT_DATA_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
     pub nft_data: Account<'info, NftData>,
     #[account(mut)]
     pub mint: Account<'info, Mint>,
@@ -553,10 +674,14 @@ pub struct TransferCrossChain<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(token_id: u64)]
 pub struct HandleCrossChainReceive<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
     pub state: Account<'info, ProgramState>,
     #[account(
         init,
@@ -587,12 +712,21 @@ pub struct HandleCrossChainReceive<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(token_id: u64)]
 pub struct RevertTransfer<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
     pub state: Account<'info, ProgramState>,
-    #[account(mut, seeds = [b"nft", token_id.to_le_bytes().as_ref()], bump)]
+    #[account(
+        mut,
+        seeds = [b"nft", token_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = nft_data.version == NFT_DATA_VERSION @ ErrorCode::UnsupportedAccountVersion
+    )]
     pub nft_data: Account<'info, NftData>,
     #[account(init, payer = payer, mint::decimals = 0, mint::authority = mint_authority)]
     pub mint: Account<'info, Mint>,
@@ -609,11 +743,20 @@ pub struct RevertTransfer<'info> {
 
 #[derive(Accounts)]
 pub struct TokenQuery<'info> {
+    #[account(constraint = state.version == PROGRAM_STATE_VERSION @ ErrorCode::UnsupportedAccountVersion)]
     pub state: Account<'info, ProgramState>,
     pub enumerable_data: Account<'info, EnumerableData>,
 }
 
 // Data structs
+
+/// Current layout version of `ProgramState`
+pub const PROGRAM_STATE_VERSION: u8 = 1;
+/// Current layout version of `ChainConnection`
+pub const CHAIN_CONNECTION_VERSION: u8 = 1;
+/// Current layout version of `NftData`
+pub const NFT_DATA_VERSION: u8 = 1;
+
 #[account]
 #[derive(InitSpace)]
 pub struct ProgramState {
@@ -629,6 +772,7 @@ pub struct ProgramState {
     pub next_token_id: u64,
     pub total_supply: u64,
     pub is_initialized: bool,
+    pub version: u8,
 }
 
 #[account]
@@ -638,6 +782,7 @@ pub struct ChainConnection {
     #[max_len(200)]
     pub contract_address: String,
     pub is_active: bool,
+    pub version: u8,
 }
 
 #[account]
@@ -651,6 +796,25 @@ pub struct NftData {
     pub original_chain: Option<u64>,
     pub pending_transfer: Option<PendingTransfer>,
     pub mint_timestamp: i64,
+    #[max_len(MAX_ATTRIBUTES)]
+    pub attributes: Vec<NftAttribute>,
+    pub version: u8,
+}
+
+/// Maximum number of on-chain attributes stored per token
+pub const MAX_ATTRIBUTES: usize = 10;
+/// Maximum length in bytes of an attribute key
+pub const MAX_ATTRIBUTE_KEY_LEN: usize = 32;
+/// Maximum length in bytes of an attribute value
+pub const MAX_ATTRIBUTE_VALUE_LEN: usize = 64;
+
+/// On-chain key/value trait carried with the token across chains
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct NftAttribute {
+    #[max_len(MAX_ATTRIBUTE_KEY_LEN)]
+    pub key: String,
+    #[max_len(MAX_ATTRIBUTE_VALUE_LEN)]
+    pub value: String,
 }
 
 #[account]
@@ -680,8 +844,15 @@ pub struct NFTOrigin {
 }
 
 // Events
+
+/// Version of the event schema, carried in every event emitted by the program
+/// Version 1 is the first released schema of the events below, and any later change
+/// to an event layout must increment it
+pub const EVENT_SCHEMA_VERSION: u8 = 1;
+
 #[event]
 pub struct ProgramInitialized {
+    pub version: u8,
     pub authority: Pubkey,
     pub name: String,
     pub symbol: String,
@@ -690,32 +861,52 @@ pub struct ProgramInitialized {
 
 #[event]
 pub struct GatewayUpdated {
+    pub version: u8,
     pub new_gateway: Pubkey,
 }
 
 #[event]
 pub struct GasLimitUpdated {
+    pub version: u8,
     pub new_gas_limit: u64,
 }
 
 #[event]
 pub struct ChainConnected {
+    pub version: u8,
     pub chain_id: u64,
     pub contract_address: String,
 }
 
+#[event]
+pub struct ChainDisconnected {
+    pub version: u8,
+    pub chain_id: u64,
+    pub contract_address: String,
+}
+
+#[event]
+pub struct AuthorityUpdated {
+    pub version: u8,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
 #[event]
 pub struct ContractPaused {
+    pub version: u8,
     pub by: Pubkey,
 }
 
 #[event]
 pub struct ContractUnpaused {
+    pub version: u8,
     pub by: Pubkey,
 }
 
 #[event]
 pub struct TokenMinted {
+    pub version: u8,
     pub recipient: Pubkey,
     pub token_id: u64,
     pub uri: String,
@@ -723,27 +914,36 @@ pub struct TokenMinted {
 
 #[event]
 pub struct TokenBurned {
+    pub version: u8,
     pub owner: Pubkey,
     pub token_id: u64,
+    pub uri: String,
 }
 
 #[event]
 pub struct TokenTransfer {
+    pub version: u8,
+    pub sender: Pubkey,
+    pub mint: Pubkey,
     pub receiver: String,
     pub destination: u64,
     pub token_id: u64,
     pub uri: String,
+    pub attributes: Vec<NftAttribute>,
 }
 
 #[event]
 pub struct TokenTransferReceived {
+    pub version: u8,
     pub recipient: Pubkey,
     pub token_id: u64,
     pub uri: String,
+    pub attributes: Vec<NftAttribute>,
 }
 
 #[event]
 pub struct TokenTransferReverted {
+    pub version: u8,
     pub owner: Pubkey,
     pub token_id: u64,
     pub uri: String,
@@ -768,5 +968,137 @@ pub enum ErrorCode {
     AlreadyPaused,
     #[msg("Contract is not paused")]
     NotPaused,
+    #[msg("Too many attributes")]
+    TooManyAttributes,
+    #[msg("Attribute key is empty or attribute exceeds maximum length")]
+    InvalidAttribute,
+    #[msg("Account version is not supported, migrate it first")]
+    UnsupportedAccountVersion,
+    #[msg("Account is already at the current version")]
+    AccountAlreadyMigrated,
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{Discriminator, Event, InstructionData};
+
+    fn attribute(key_len: usize, value_len: usize) -> NftAttribute {
+        NftAttribute {
+            key: "k".repeat(key_len),
+            value: "v".repeat(value_len),
+        }
+    }
+
+    #[test]
+    fn test_validate_attributes_at_bounds() {
+        // Arrange
+        let attributes =
+            vec![attribute(MAX_ATTRIBUTE_KEY_LEN, MAX_ATTRIBUTE_VALUE_LEN); MAX_ATTRIBUTES];
+
+        // Act
+        let at_bounds = validate_attributes(&attributes);
+        let empty = validate_attributes(&[]);
+
+        // Assert
+        assert!(at_bounds.is_ok());
+        assert!(empty.is_ok());
+    }
+
+    #[test]
+    fn test_validate_attributes_out_of_bounds() {
+        // Arrange
+        let too_many = vec![attribute(1, 1); MAX_ATTRIBUTES + 1];
+        let empty_key = vec![attribute(0, 1)];
+        let long_key = vec![attribute(MAX_ATTRIBUTE_KEY_LEN + 1, 1)];
+        let long_value = vec![attribute(1, MAX_ATTRIBUTE_VALUE_LEN + 1)];
+
+        // Act
+        let too_many = validate_attributes(&too_many);
+        let empty_key = validate_attributes(&empty_key);
+        let long_key = validate_attributes(&long_key);
+        let long_value = validate_attributes(&long_value);
+
+        // Assert
+        assert_eq!(too_many.err(), Some(ErrorCode::TooManyAttributes.into()));
+        assert_eq!(empty_key.err(), Some(ErrorCode::InvalidAttribute.into()));
+        assert_eq!(long_key.err(), Some(ErrorCode::InvalidAttribute.into()));
+        assert_eq!(long_value.err(), Some(ErrorCode::InvalidAttribute.into()));
+    }
+
+    #[test]
+    fn test_nft_data_space_fits_max_attributes() {
+        // Arrange
+        let nft_data = NftData {
+            token_id: u64::MAX,
+            uri: "u".repeat(500),
+            owner: Pubkey::new_unique(),
+            is_burned: false,
+            original_chain: Some(1),
+            pending_transfer: Some(PendingTransfer {
+                destination_chain: 1,
+                receiver: "r".repeat(200),
+                timestamp: 0,
+            }),
+            mint_timestamp: 0,
+            attributes: vec![
+                attribute(MAX_ATTRIBUTE_KEY_LEN, MAX_ATTRIBUTE_VALUE_LEN);
+                MAX_ATTRIBUTES
+            ],
+            version: NFT_DATA_VERSION,
+        };
+        let mut data = vec![0u8; 8 + NftData::INIT_SPACE];
+
+        // Act
+        let result = nft_data.try_serialize(&mut &mut data[..]);
+
+        // Assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_attributes_cross_chain_round_trip() {
+        // Arrange
+        let attributes = vec![
+            NftAttribute {
+                key: "rarity".to_string(),
+                value: "legendary".to_string(),
+            },
+            attribute(MAX_ATTRIBUTE_KEY_LEN, MAX_ATTRIBUTE_VALUE_LEN),
+        ];
+        let outbound = TokenTransfer {
+            version: EVENT_SCHEMA_VERSION,
+            sender: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            receiver: "0x1234".to_string(),
+            destination: 7001,
+            token_id: 42,
+            uri: "https://example.com/42".to_string(),
+            attributes: attributes.clone(),
+        };
+
+        // Act
+        let event_data = outbound.data();
+        let observed =
+            TokenTransfer::try_from_slice(&event_data[TokenTransfer::DISCRIMINATOR.len()..])
+                .unwrap();
+        let inbound_data = crate::instruction::HandleCrossChainReceive {
+            token_id: observed.token_id,
+            uri: observed.uri,
+            attributes: observed.attributes,
+            sender_chain_id: 7001,
+            original_sender: "0x1234".to_string(),
+        }
+        .data();
+        let inbound = crate::instruction::HandleCrossChainReceive::try_from_slice(
+            &inbound_data[crate::instruction::HandleCrossChainReceive::DISCRIMINATOR.len()..],
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(inbound.token_id, 42);
+        assert_eq!(inbound.attributes, attributes);
+        assert!(validate_attributes(&inbound.attributes).is_ok());
+    }
+}
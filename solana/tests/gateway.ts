@@ -10,6 +10,7 @@ import { getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
 import { Connected } from "../target/types/connected";
 import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
 import { ConnectedSpl } from "../target/types/connected_spl";
+import { MockAmm } from "../target/types/mock_amm";
 import { ComputeBudgetProgram } from "@solana/web3.js";
 
 const ec = new EC("secp256k1");
@@ -104,6 +105,7 @@ async function depositSplTokens(
       revertOptions
     )
     .accounts({
+      tokenProgram: spl.TOKEN_PROGRAM_ID,
       from: tokenAccount.address,
       to: pda_ata.address,
       mintAccount: mint.publicKey,
@@ -148,6 +150,7 @@ async function withdrawSplToken(
       nonce
     )
     .accounts({
+      tokenProgram: spl.TOKEN_PROGRAM_ID,
       pdaAta: from,
       mintAccount: mint.publicKey,
       recipientAta: to,
@@ -156,6 +159,70 @@ async function withdrawSplToken(
     .rpc({ commitment: "processed" });
 }
 
+function withdrawSolMessageHash(amount, nonce, recipient) {
+  const buffer = Buffer.concat([
+    Buffer.from("ZETACHAIN", "utf-8"),
+    Buffer.from([0x01]),
+    chain_id_bn.toArrayLike(Buffer, "be", 8),
+    nonce.toArrayLike(Buffer, "be", 8),
+    amount.toArrayLike(Buffer, "be", 8),
+    recipient.toBuffer(),
+  ]);
+  return keccak256(buffer);
+}
+
+async function withdrawSol(
+  amount,
+  nonce,
+  recipient,
+  gatewayProgram: Program<Gateway>
+) {
+  const message_hash = withdrawSolMessageHash(amount, nonce, recipient);
+  const signature = keyPair.sign(message_hash, "hex");
+  const { r, s, recoveryParam } = signature;
+  const signatureBuffer = Buffer.concat([
+    r.toArrayLike(Buffer, "be", 32),
+    s.toArrayLike(Buffer, "be", 32),
+  ]);
+  return gatewayProgram.methods
+    .withdraw(
+      amount,
+      Array.from(signatureBuffer),
+      Number(recoveryParam),
+      Array.from(message_hash),
+      nonce
+    )
+    .accounts({ recipient })
+    .rpc({ commitment: "processed" });
+}
+
+// Decodes events emitted through emit_cpi! from the inner instructions of a transaction
+async function getCpiEvents(
+  program: Program<Gateway>,
+  conn: anchor.web3.Connection,
+  txsig: string
+) {
+  await conn.confirmTransaction(txsig, "confirmed");
+  const tx = await conn.getTransaction(txsig, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const events = [];
+  for (const inner of tx.meta.innerInstructions ?? []) {
+    for (const ix of inner.instructions) {
+      const programId =
+        tx.transaction.message.staticAccountKeys[ix.programIdIndex];
+      if (!programId.equals(program.programId)) continue;
+      const ixData = anchor.utils.bytes.bs58.decode(ix.data);
+      const event = program.coder.events.decode(
+        anchor.utils.bytes.base64.encode(ixData.subarray(8))
+      );
+      if (event) events.push(event);
+    }
+  }
+  return events;
+}
+
 describe("Gateway", () => {
   // Configure the client to use the local cluster.
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -164,6 +231,7 @@ describe("Gateway", () => {
   const connectedProgram = anchor.workspace.Connected as Program<Connected>;
   const connectedSPLProgram = anchor.workspace
     .ConnectedSPL as Program<ConnectedSpl>;
+  const mockAmmProgram = anchor.workspace.MockAmm as Program<MockAmm>;
   const wallet = anchor.workspace.Gateway.provider.wallet.payer;
   const mint = anchor.web3.Keypair.generate();
   const mint_fake = anchor.web3.Keypair.generate(); // for testing purpose
@@ -177,6 +245,11 @@ describe("Gateway", () => {
     seeds,
     gatewayProgram.programId
   );
+  // gateway caller PDA signing the calls to the connected program
+  const [connectedCaller] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("caller", "utf-8"), connectedProgram.programId.toBuffer()],
+    gatewayProgram.programId
+  );
 
   it("Initializes the program", async () => {
     await gatewayProgram.methods.initialize(tssAddress, chain_id_bn).rpc();
@@ -190,6 +263,19 @@ describe("Gateway", () => {
     }
   });
 
+  it("Upgrade PDA fails if PDA already uses the current layout", async () => {
+    const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
+    expect(pdaAccountData.version).to.eq(1);
+
+    try {
+      await gatewayProgram.methods.upgradePda().rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("PdaAlreadyUpgraded");
+    }
+  });
+
   it("Mint a SPL USDC token", async () => {
     // now deploying a fake USDC SPL Token
     // 1. create a mint account
@@ -263,6 +349,22 @@ describe("Gateway", () => {
       seeds,
       gatewayProgram.programId
     );
+    const entry = await gatewayProgram.account.whitelistEntry.fetch(
+      entryAddress
+    );
+    expect(entry.version).to.eq(1);
+
+    // entries created in the current layout have nothing to migrate
+    try {
+      await gatewayProgram.methods
+        .upgradeWhitelistEntry()
+        .accounts({ mintAccount: mint.publicKey })
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("WhitelistEntryAlreadyUpgraded");
+    }
 
     try {
       seeds = [
@@ -303,6 +405,7 @@ describe("Gateway", () => {
           null
         )
         .accounts({
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
           from: tokenAccount.address,
           to: pda_ata.address,
           mintAccount: mint.publicKey,
@@ -343,6 +446,7 @@ describe("Gateway", () => {
         null
       )
       .accounts({
+        tokenProgram: spl.TOKEN_PROGRAM_ID,
         from: tokenAccount.address,
         to: pda_ata.address,
         mintAccount: mint.publicKey,
@@ -385,6 +489,7 @@ describe("Gateway", () => {
           revertOptions
         )
         .accounts({
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
           from: tokenAccount.address,
           to: wallet_ata,
           mintAccount: mint.publicKey,
@@ -407,6 +512,7 @@ describe("Gateway", () => {
         revertOptions
       )
       .accounts({
+        tokenProgram: spl.TOKEN_PROGRAM_ID,
         from: tokenAccount.address,
         to: pda_ata.address,
         mintAccount: mint.publicKey,
@@ -441,6 +547,7 @@ describe("Gateway", () => {
           revertOptions
         )
         .accounts({
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
           from: fake_tokenAccount.address,
           to: fake_pda_ata.address,
           mintAccount: mint.publicKey,
@@ -482,6 +589,7 @@ describe("Gateway", () => {
           revertOptions
         )
         .accounts({
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
           from: tokenAccount.address,
           to: fake_pda_ata.address,
           mintAccount: mint_fake.publicKey,
@@ -566,6 +674,7 @@ describe("Gateway", () => {
           nonce2
         )
         .accounts({
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
           pdaAta: pda_ata,
           mintAccount: mint_fake.publicKey,
           recipientAta: wallet_ata,
@@ -575,7 +684,7 @@ describe("Gateway", () => {
       throw new Error("Expected error not thrown"); // This line will make the test fail if no error is thrown
     } catch (err) {
       expect(err).to.be.instanceof(anchor.AnchorError);
-      expect(err.message).to.include("ConstraintAssociated");
+      expect(err.message).to.include("AccountNotInitialized");
       const account4 = await spl.getAccount(conn, pda_ata);
       expect(account4.amount).to.be.eq(4_500_000n);
     }
@@ -594,6 +703,11 @@ describe("Gateway", () => {
   });
 
   it("Deposit through connected program", async () => {
+    const [gatewayEventAuthority] =
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("__event_authority", "utf-8")],
+        gatewayProgram.programId
+      );
     const balanceBefore = await conn.getBalance(pdaAccount);
     await connectedProgram.methods
       .triggerDeposit(
@@ -604,6 +718,7 @@ describe("Gateway", () => {
       .accounts({
         gatewayPda: pdaAccount,
         gatewayProgram: gatewayProgram.programId,
+        gatewayEventAuthority,
       })
       .rpc();
 
@@ -612,6 +727,192 @@ describe("Gateway", () => {
     expect(balanceAfter - balanceBefore).to.eq(1_000_000_000 + 2_000_000);
   });
 
+  it("Update deposit fee and deposit charges the new fee", async () => {
+    await gatewayProgram.methods
+      .updateDepositFee(
+        new anchor.BN(3_000_000),
+        new anchor.BN(3_000_000),
+        Array(64).fill(0),
+        0,
+        Array(32).fill(0),
+        new anchor.BN(0)
+      )
+      .rpc();
+
+    const balanceBefore = await conn.getBalance(pdaAccount);
+    await gatewayProgram.methods
+      .deposit(new anchor.BN(1_000_000), Array.from(address), revertOptions)
+      .rpc({ commitment: "processed" });
+    const balanceAfter = await conn.getBalance(pdaAccount);
+    expect(balanceAfter - balanceBefore).to.eq(1_000_000 + 3_000_000);
+
+    await gatewayProgram.methods
+      .updateDepositFee(
+        new anchor.BN(2_000_000),
+        new anchor.BN(2_000_000),
+        Array(64).fill(0),
+        0,
+        Array(32).fill(0),
+        new anchor.BN(0)
+      )
+      .rpc();
+  });
+
+  it("Update deposit fee fails above the maximum fee", async () => {
+    try {
+      await gatewayProgram.methods
+        .updateDepositFee(
+          new anchor.BN(100_000_001),
+          new anchor.BN(2_000_000),
+          Array(64).fill(0),
+          0,
+          Array(32).fill(0),
+          new anchor.BN(0)
+        )
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("DepositFeeTooHigh");
+    }
+  });
+
+  it("Update deposit fee fails if wrong authority", async () => {
+    try {
+      await gatewayProgram.methods
+        .updateDepositFee(
+          new anchor.BN(0),
+          new anchor.BN(0),
+          Array(64).fill(0),
+          0,
+          Array(32).fill(0),
+          new anchor.BN(0)
+        )
+        .accounts({ signer: random_account.publicKey })
+        .signers([random_account])
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("SignerIsNotAuthority");
+    }
+  });
+
+  it("Deposit through fee exempt connected program is not charged a fee", async () => {
+    const [gatewayEventAuthority] =
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("__event_authority", "utf-8")],
+        gatewayProgram.programId
+      );
+    const [feeExemption] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("fee_exemption", "utf-8"),
+        connectedProgram.programId.toBuffer(),
+      ],
+      gatewayProgram.programId
+    );
+    await gatewayProgram.methods
+      .addFeeExemption(connectedProgram.programId)
+      .rpc();
+
+    const balanceBefore = await conn.getBalance(pdaAccount);
+    await connectedProgram.methods
+      .triggerDeposit(
+        new anchor.BN(1_000_000_000),
+        Array.from(address),
+        revertOptions
+      )
+      .accounts({
+        gatewayPda: pdaAccount,
+        gatewayProgram: gatewayProgram.programId,
+        gatewayEventAuthority,
+        gatewayFeeExemption: feeExemption,
+        instructionSysvarAccount: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+    const balanceAfter = await conn.getBalance(pdaAccount);
+    expect(balanceAfter - balanceBefore).to.eq(1_000_000_000);
+
+    // exemption is rejected when gateway is called directly
+    try {
+      await gatewayProgram.methods
+        .deposit(new anchor.BN(1_000_000), Array.from(address), revertOptions)
+        .accounts({ feeExemption })
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("InvalidFeeExemption");
+    }
+
+    await gatewayProgram.methods
+      .removeFeeExemption()
+      .accounts({ feeExemption })
+      .rpc();
+  });
+
+  it("Deposit accumulates fees and withdraw fees sends them to the treasury", async () => {
+    const treasury = anchor.web3.Keypair.generate();
+    await gatewayProgram.methods.updateTreasury(treasury.publicKey).rpc();
+
+    const feesBefore = (await gatewayProgram.account.pda.fetch(pdaAccount))
+      .collectedFees;
+    await gatewayProgram.methods
+      .deposit(new anchor.BN(1_000_000), Array.from(address), revertOptions)
+      .rpc({ commitment: "processed" });
+    const pdaAfterDeposit = await gatewayProgram.account.pda.fetch(pdaAccount);
+    expect(pdaAfterDeposit.collectedFees.sub(feesBefore).toNumber()).to.eq(
+      2_000_000
+    );
+
+    const pdaBalanceBefore = await conn.getBalance(pdaAccount);
+    await gatewayProgram.methods
+      .withdrawFees(new anchor.BN(2_000_000))
+      .accounts({ treasury: treasury.publicKey })
+      .rpc({ commitment: "processed" });
+    const pdaBalanceAfter = await conn.getBalance(pdaAccount);
+    expect(pdaBalanceBefore - pdaBalanceAfter).to.eq(2_000_000);
+    expect(await conn.getBalance(treasury.publicKey)).to.eq(2_000_000);
+
+    const pdaAfterWithdraw = await gatewayProgram.account.pda.fetch(
+      pdaAccount
+    );
+    expect(
+      pdaAfterDeposit.collectedFees.sub(pdaAfterWithdraw.collectedFees).toNumber()
+    ).to.eq(2_000_000);
+
+    await gatewayProgram.methods.updateTreasury(wallet.publicKey).rpc();
+  });
+
+  it("Withdraw fees above collected fees should fail", async () => {
+    const pda = await gatewayProgram.account.pda.fetch(pdaAccount);
+    try {
+      await gatewayProgram.methods
+        .withdrawFees(pda.collectedFees.add(new anchor.BN(1)))
+        .accounts({ treasury: pda.treasury })
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("InsufficientCollectedFees");
+    }
+  });
+
+  it("Withdraw fees fails if wrong authority", async () => {
+    const pda = await gatewayProgram.account.pda.fetch(pdaAccount);
+    try {
+      await gatewayProgram.methods
+        .withdrawFees(new anchor.BN(1))
+        .accounts({ signer: random_account.publicKey, treasury: pda.treasury })
+        .signers([random_account])
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("SignerIsNotAuthority");
+    }
+  });
+
   it("Deposit and withdraw 0.5 SOL from Gateway with ECDSA signature", async () => {
     const balanceBefore = await conn.getBalance(pdaAccount);
     await gatewayProgram.methods
@@ -661,22 +962,72 @@ describe("Gateway", () => {
     expect(bal3).to.be.gte(500_000_000);
   });
 
-  it("Withdraw with wrong nonce should fail", async () => {
-    const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
-    const nonce = pdaAccountData.nonce;
-    const amount = new anchor.BN(500000000);
-    const to = await spl.getAssociatedTokenAddress(
+  it("Deposit wSOL unwraps it and credits it as SOL", async () => {
+    const wsolAccount = await spl.createWrappedNativeAccount(
+      conn,
+      wallet,
+      wallet.publicKey,
+      500_000_000
+    );
+    const balanceBefore = await conn.getBalance(pdaAccount);
+
+    const txsig = await gatewayProgram.methods
+      .depositWsol(Array.from(address), revertOptions)
+      .accounts({
+        wsolAccount,
+        tokenProgram: spl.TOKEN_PROGRAM_ID,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const balanceAfter = await conn.getBalance(pdaAccount);
+    // amount + deposit fee
+    expect(balanceAfter - balanceBefore).to.eq(500_000_000 + 2_000_000);
+    expect(await conn.getAccountInfo(wsolAccount)).to.be.null;
+
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    expect(events[0].name).to.eq("deposited");
+    expect(events[0].data.amount.toNumber()).to.eq(500_000_000);
+    expect(events[0].data.mint).to.be.null;
+  });
+
+  it("Deposit wSOL with a non-native token account should fail", async () => {
+    const tokenAccount = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
       mint.publicKey,
       wallet.publicKey
     );
+    try {
+      await gatewayProgram.methods
+        .depositWsol(Array.from(address), revertOptions)
+        .accounts({
+          wsolAccount: tokenAccount.address,
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("NotNativeMint");
+    }
+  });
 
+  it("Withdraw SOL as wSOL to the recipient ATA", async () => {
+    const recipient = anchor.web3.Keypair.generate().publicKey;
+    const recipientAta = spl.getAssociatedTokenAddressSync(
+      spl.NATIVE_MINT,
+      recipient
+    );
+    const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
+    const nonce = pdaAccountData.nonce;
+    const amount = new anchor.BN(100_000_000);
     const buffer = Buffer.concat([
       Buffer.from("ZETACHAIN", "utf-8"),
-      Buffer.from([0x01]),
+      Buffer.from([11]),
       chain_id_bn.toArrayLike(Buffer, "be", 8),
       nonce.toArrayLike(Buffer, "be", 8),
       amount.toArrayLike(Buffer, "be", 8),
-      to.toBuffer(),
+      recipient.toBuffer(),
     ]);
     const message_hash = keccak256(buffer);
     const signature = keyPair.sign(message_hash, "hex");
@@ -686,42 +1037,45 @@ describe("Gateway", () => {
       s.toArrayLike(Buffer, "be", 32),
     ]);
 
-    try {
-      await gatewayProgram.methods
-        .withdraw(
-          amount,
-          Array.from(signatureBuffer),
-          Number(recoveryParam),
-          Array.from(message_hash),
-          nonce.subn(1)
-        )
-        .accounts({
-          recipient: to,
-        })
-        .rpc();
-      throw new Error("Expected error not thrown");
-    } catch (err) {
-      expect(err).to.be.instanceof(anchor.AnchorError);
-      expect(err.message).to.include("NonceMismatch.");
-    }
+    await gatewayProgram.methods
+      .withdrawWsol(
+        amount,
+        Array.from(signatureBuffer),
+        Number(recoveryParam),
+        Array.from(message_hash),
+        nonce
+      )
+      .accounts({
+        recipient,
+        recipientAta,
+        nativeMint: spl.NATIVE_MINT,
+      })
+      .rpc();
+
+    const recipientAtaAccount = await spl.getAccount(conn, recipientAta);
+    expect(recipientAtaAccount.isNative).to.be.true;
+    expect(Number(recipientAtaAccount.amount)).to.eq(100_000_000);
   });
 
-  it("Withdraw with wrong msg hash should fail", async () => {
+  it("Increment nonce v2 refunds the amount of a failed outbound", async () => {
+    const abortAddress = anchor.web3.Keypair.generate().publicKey;
+    const [rateLimit] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rate_limit", "utf-8"), anchor.web3.PublicKey.default.toBuffer()],
+      gatewayProgram.programId
+    );
     const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
     const nonce = pdaAccountData.nonce;
-    const amount = new anchor.BN(500000000);
-    const to = await spl.getAssociatedTokenAddress(
-      mint.publicKey,
-      wallet.publicKey
-    );
-
+    const amount = new anchor.BN(100_000_000);
+    const originalInstructionId = 5; // ExecuteSol
+    const failureCode = 0; // CallFailed
     const buffer = Buffer.concat([
       Buffer.from("ZETACHAIN", "utf-8"),
-      Buffer.from([0x01]),
+      Buffer.from([16]),
       chain_id_bn.toArrayLike(Buffer, "be", 8),
-      nonce.subn(1).toArrayLike(Buffer, "be", 8), // wrong nonce
+      nonce.toArrayLike(Buffer, "be", 8),
       amount.toArrayLike(Buffer, "be", 8),
-      to.toBuffer(),
+      Buffer.from([originalInstructionId, failureCode]),
+      abortAddress.toBuffer(),
     ]);
     const message_hash = keccak256(buffer);
     const signature = keyPair.sign(message_hash, "hex");
@@ -730,17 +1084,413 @@ describe("Gateway", () => {
       r.toArrayLike(Buffer, "be", 32),
       s.toArrayLike(Buffer, "be", 32),
     ]);
-
-    try {
-      await gatewayProgram.methods
-        .withdraw(
+    const incrementNonceV2 = (
+      refundAddress: anchor.web3.PublicKey | null,
+      instructionId: object = { executeSol: {} }
+    ) =>
+      gatewayProgram.methods
+        .incrementNonceV2(
           amount,
           Array.from(signatureBuffer),
           Number(recoveryParam),
           Array.from(message_hash),
-          nonce
+          nonce,
+          { callFailed: {} },
+          instructionId
         )
-        .accounts({
+        .accountsPartial({
+          refundAddress,
+          rateLimit: refundAddress ? rateLimit : null,
+          mintAccount: null,
+          pdaAta: null,
+          refundAta: null,
+          tokenProgram: null,
+          associatedTokenProgram: null,
+        });
+
+    // omitting the refund does not match the signed message
+    try {
+      await incrementNonceV2(null).rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("MessageHashMismatch");
+    }
+
+    // a nonce increment is not an outbound that can fail
+    try {
+      await incrementNonceV2(abortAddress, { incrementNonce: {} }).rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("InvalidInstructionData");
+    }
+
+    const pdaBalanceBefore = await conn.getBalance(pdaAccount);
+    const txsig = await incrementNonceV2(abortAddress).rpc({
+      commitment: "confirmed",
+    });
+
+    const pdaBalanceAfter = await conn.getBalance(pdaAccount);
+    expect(pdaBalanceBefore - pdaBalanceAfter).to.eq(100_000_000);
+    expect(await conn.getBalance(abortAddress)).to.eq(100_000_000);
+    const pdaAccountDataAfter = await gatewayProgram.account.pda.fetch(
+      pdaAccount
+    );
+    expect(pdaAccountDataAfter.nonce.toNumber()).to.eq(nonce.toNumber() + 1);
+
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    expect(events[0].name).to.eq("outboundFailed");
+    expect(events[0].data.failureCode).to.deep.eq({ callFailed: {} });
+    expect(events[0].data.originalInstructionId).to.deep.eq({
+      executeSol: {},
+    });
+    expect(events[0].data.refundAddress.toBase58()).to.eq(
+      abortAddress.toBase58()
+    );
+    expect(events[0].data.mint).to.be.null;
+  });
+
+  it("Withdraw SOL over rate limit should fail", async () => {
+    const solMint = anchor.web3.PublicKey.default;
+    await gatewayProgram.methods
+      .setRateLimit(
+        solMint,
+        new anchor.BN(1_000_000),
+        new anchor.BN(1_500_000),
+        new anchor.BN(1_000_000),
+        new anchor.BN(0),
+        new anchor.BN(0)
+      )
+      .rpc();
+
+    let nonce = (await gatewayProgram.account.pda.fetch(pdaAccount)).nonce;
+    try {
+      await withdrawSol(
+        new anchor.BN(2_000_000),
+        nonce,
+        wallet.publicKey,
+        gatewayProgram
+      );
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("WithdrawalAboveLimit");
+    }
+
+    await withdrawSol(
+      new anchor.BN(1_000_000),
+      nonce,
+      wallet.publicKey,
+      gatewayProgram
+    );
+    nonce = (await gatewayProgram.account.pda.fetch(pdaAccount)).nonce;
+    try {
+      await withdrawSol(
+        new anchor.BN(1_000_000),
+        nonce,
+        wallet.publicKey,
+        gatewayProgram
+      );
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("RateLimitExceeded");
+    }
+
+    // raising the window cap keeps the amount already counted
+    await gatewayProgram.methods
+      .updateRateLimit(
+        solMint,
+        new anchor.BN(1_000_000),
+        new anchor.BN(2_000_000),
+        new anchor.BN(1_000_000),
+        new anchor.BN(0),
+        new anchor.BN(0)
+      )
+      .rpc();
+    await withdrawSol(
+      new anchor.BN(1_000_000),
+      nonce,
+      wallet.publicKey,
+      gatewayProgram
+    );
+
+    await gatewayProgram.methods.removeRateLimit(solMint).rpc();
+  });
+
+  it("Withdraw SOL above delay threshold is queued until finalized", async () => {
+    const solMint = anchor.web3.PublicKey.default;
+    await gatewayProgram.methods
+      .setRateLimit(
+        solMint,
+        new anchor.BN(10_000_000),
+        new anchor.BN(100_000_000),
+        new anchor.BN(1_000_000),
+        new anchor.BN(1_000_000),
+        new anchor.BN(5)
+      )
+      .rpc();
+
+    const recipient = anchor.web3.Keypair.generate().publicKey;
+    const nonce = (await gatewayProgram.account.pda.fetch(pdaAccount)).nonce;
+    const messageHash = withdrawSolMessageHash(
+      new anchor.BN(2_000_000),
+      nonce,
+      recipient
+    );
+    const [pendingWithdrawal] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_withdrawal", "utf-8"), messageHash],
+      gatewayProgram.programId
+    );
+    await withdrawSol(new anchor.BN(2_000_000), nonce, recipient, gatewayProgram);
+
+    const pending = await gatewayProgram.account.pendingWithdrawal.fetch(
+      pendingWithdrawal
+    );
+    expect(pending.amount.toNumber()).to.eq(2_000_000);
+    expect(pending.recipient.toBase58()).to.eq(recipient.toBase58());
+    expect(await conn.getBalance(recipient)).to.eq(0);
+    // nonce is consumed when the withdrawal is queued
+    expect(
+      (await gatewayProgram.account.pda.fetch(pdaAccount)).nonce.toNumber()
+    ).to.eq(nonce.toNumber() + 1);
+
+    try {
+      await gatewayProgram.methods
+        .finalizeWithdrawal(Array.from(messageHash))
+        .accounts({ recipient })
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("WithdrawalNotReleasable");
+    }
+
+    while ((await conn.getSlot("processed")) < pending.releaseSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+    await gatewayProgram.methods
+      .finalizeWithdrawal(Array.from(messageHash))
+      .accounts({ recipient })
+      .rpc({ commitment: "processed" });
+    expect(await conn.getBalance(recipient)).to.eq(2_000_000);
+    expect(await conn.getAccountInfo(pendingWithdrawal)).to.be.null;
+  });
+
+  it("Cancel queued withdrawal keeps funds in the gateway", async () => {
+    const recipient = anchor.web3.Keypair.generate().publicKey;
+    const nonce = (await gatewayProgram.account.pda.fetch(pdaAccount)).nonce;
+    const messageHash = withdrawSolMessageHash(
+      new anchor.BN(2_000_000),
+      nonce,
+      recipient
+    );
+    const [rateLimit] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("rate_limit", "utf-8"),
+        anchor.web3.PublicKey.default.toBuffer(),
+      ],
+      gatewayProgram.programId
+    );
+    const windowBefore = (await gatewayProgram.account.rateLimit.fetch(rateLimit))
+      .windowAmount;
+    await withdrawSol(new anchor.BN(2_000_000), nonce, recipient, gatewayProgram);
+
+    try {
+      await gatewayProgram.methods
+        .cancelWithdrawal(Array.from(messageHash))
+        .accounts({ signer: random_account.publicKey })
+        .signers([random_account])
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("SignerIsNotAuthority");
+    }
+
+    const balanceBefore = await conn.getBalance(pdaAccount);
+    await gatewayProgram.methods.cancelWithdrawal(Array.from(messageHash)).rpc();
+    expect(await conn.getBalance(pdaAccount)).to.eq(balanceBefore);
+    // the cancelled amount no longer counts against the rate limit window
+    const windowAfter = (await gatewayProgram.account.rateLimit.fetch(rateLimit))
+      .windowAmount;
+    expect(windowAfter.toNumber()).to.be.at.most(windowBefore.toNumber());
+    expect(await conn.getBalance(recipient)).to.eq(0);
+    expect(
+      (await gatewayProgram.account.pda.fetch(pdaAccount)).nonce.toNumber()
+    ).to.eq(nonce.toNumber() + 1);
+
+    await gatewayProgram.methods
+      .removeRateLimit(anchor.web3.PublicKey.default)
+      .rpc();
+  });
+
+  it("Cancel queued wSOL withdrawal credits the SOL rate limit", async () => {
+    const solMint = anchor.web3.PublicKey.default;
+    await gatewayProgram.methods
+      .setRateLimit(
+        solMint,
+        new anchor.BN(10_000_000),
+        new anchor.BN(100_000_000),
+        new anchor.BN(1_000_000),
+        new anchor.BN(1_000_000),
+        new anchor.BN(1_000)
+      )
+      .rpc();
+    const [rateLimit] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rate_limit", "utf-8"), solMint.toBuffer()],
+      gatewayProgram.programId
+    );
+
+    const recipient = anchor.web3.Keypair.generate().publicKey;
+    const recipientAta = spl.getAssociatedTokenAddressSync(
+      spl.NATIVE_MINT,
+      recipient
+    );
+    const nonce = (await gatewayProgram.account.pda.fetch(pdaAccount)).nonce;
+    const amount = new anchor.BN(2_000_000);
+    const messageHash = keccak256(
+      Buffer.concat([
+        Buffer.from("ZETACHAIN", "utf-8"),
+        Buffer.from([11]),
+        chain_id_bn.toArrayLike(Buffer, "be", 8),
+        nonce.toArrayLike(Buffer, "be", 8),
+        amount.toArrayLike(Buffer, "be", 8),
+        recipient.toBuffer(),
+      ])
+    );
+    const signature = keyPair.sign(messageHash, "hex");
+    const signatureBuffer = Buffer.concat([
+      signature.r.toArrayLike(Buffer, "be", 32),
+      signature.s.toArrayLike(Buffer, "be", 32),
+    ]);
+    await gatewayProgram.methods
+      .withdrawWsol(
+        amount,
+        Array.from(signatureBuffer),
+        Number(signature.recoveryParam),
+        Array.from(messageHash),
+        nonce
+      )
+      .accounts({
+        recipient,
+        recipientAta,
+        nativeMint: spl.NATIVE_MINT,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const [pendingWithdrawal] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_withdrawal", "utf-8"), messageHash],
+      gatewayProgram.programId
+    );
+    const pending = await gatewayProgram.account.pendingWithdrawal.fetch(
+      pendingWithdrawal,
+      "confirmed"
+    );
+    expect(pending.mint.toBase58()).to.eq(spl.NATIVE_MINT.toBase58());
+    expect(pending.rateLimitMint.toBase58()).to.eq(solMint.toBase58());
+    const windowQueued = (
+      await gatewayProgram.account.rateLimit.fetch(rateLimit, "confirmed")
+    ).windowAmount;
+    expect(windowQueued.toNumber()).to.be.at.least(2_000_000);
+
+    await gatewayProgram.methods
+      .cancelWithdrawal(Array.from(messageHash))
+      .rpc({ commitment: "confirmed" });
+
+    // the wSOL withdrawal consumed the SOL window, which the cancel credits back
+    const windowAfter = (
+      await gatewayProgram.account.rateLimit.fetch(rateLimit, "confirmed")
+    ).windowAmount;
+    expect(windowAfter.toNumber()).to.be.at.most(
+      windowQueued.toNumber() - 2_000_000
+    );
+    expect(await conn.getAccountInfo(pendingWithdrawal)).to.be.null;
+
+    await gatewayProgram.methods.removeRateLimit(solMint).rpc();
+  });
+
+  it("Withdraw with wrong nonce should fail", async () => {
+    const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
+    const nonce = pdaAccountData.nonce;
+    const amount = new anchor.BN(500000000);
+    const to = await spl.getAssociatedTokenAddress(
+      mint.publicKey,
+      wallet.publicKey
+    );
+
+    const buffer = Buffer.concat([
+      Buffer.from("ZETACHAIN", "utf-8"),
+      Buffer.from([0x01]),
+      chain_id_bn.toArrayLike(Buffer, "be", 8),
+      nonce.toArrayLike(Buffer, "be", 8),
+      amount.toArrayLike(Buffer, "be", 8),
+      to.toBuffer(),
+    ]);
+    const message_hash = keccak256(buffer);
+    const signature = keyPair.sign(message_hash, "hex");
+    const { r, s, recoveryParam } = signature;
+    const signatureBuffer = Buffer.concat([
+      r.toArrayLike(Buffer, "be", 32),
+      s.toArrayLike(Buffer, "be", 32),
+    ]);
+
+    try {
+      await gatewayProgram.methods
+        .withdraw(
+          amount,
+          Array.from(signatureBuffer),
+          Number(recoveryParam),
+          Array.from(message_hash),
+          nonce.subn(1)
+        )
+        .accounts({
+          recipient: to,
+        })
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("NonceMismatch.");
+    }
+  });
+
+  it("Withdraw with wrong msg hash should fail", async () => {
+    const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
+    const nonce = pdaAccountData.nonce;
+    const amount = new anchor.BN(500000000);
+    const to = await spl.getAssociatedTokenAddress(
+      mint.publicKey,
+      wallet.publicKey
+    );
+
+    const buffer = Buffer.concat([
+      Buffer.from("ZETACHAIN", "utf-8"),
+      Buffer.from([0x01]),
+      chain_id_bn.toArrayLike(Buffer, "be", 8),
+      nonce.subn(1).toArrayLike(Buffer, "be", 8), // wrong nonce
+      amount.toArrayLike(Buffer, "be", 8),
+      to.toBuffer(),
+    ]);
+    const message_hash = keccak256(buffer);
+    const signature = keyPair.sign(message_hash, "hex");
+    const { r, s, recoveryParam } = signature;
+    const signatureBuffer = Buffer.concat([
+      r.toArrayLike(Buffer, "be", 32),
+      s.toArrayLike(Buffer, "be", 32),
+    ]);
+
+    try {
+      await gatewayProgram.methods
+        .withdraw(
+          amount,
+          Array.from(signatureBuffer),
+          Number(recoveryParam),
+          Array.from(message_hash),
+          nonce
+        )
+        .accounts({
           recipient: to,
         })
         .rpc();
@@ -871,7 +1621,7 @@ describe("Gateway", () => {
           isWritable: false,
         },
         {
-          pubkey: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          pubkey: connectedCaller,
           isSigner: false,
           isWritable: false,
         },
@@ -902,22 +1652,20 @@ describe("Gateway", () => {
     );
   });
 
-  it("Calls execute and onCall reverts if connected program reverts", async () => {
-    await gatewayProgram.methods
-      .deposit(new anchor.BN(1_000_000_000), Array.from(address), revertOptions)
-      .rpc();
-
+  it("Calls execute and onCall fails if the caller PDA of another program is forwarded", async () => {
     const randomWallet = anchor.web3.Keypair.generate();
-    const lastMessageData = "revert";
-    const data = Buffer.from(lastMessageData, "utf-8");
-    let seeds = [Buffer.from("connected", "utf-8")];
+    const data = Buffer.from("execute_sol", "utf-8");
     const [connectedPdaAccount] = anchor.web3.PublicKey.findProgramAddressSync(
-      seeds,
+      [Buffer.from("connected", "utf-8")],
       connectedProgram.programId
     );
+    // caller PDA the gateway signs with when calling the connected SPL program
+    const [otherCaller] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("caller", "utf-8"), connectedSPLProgram.programId.toBuffer()],
+      gatewayProgram.programId
+    );
     const amount = new anchor.BN(500000000);
 
-    // signature
     const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
     const nonce = pdaAccountData.nonce;
     const buffer = Buffer.concat([
@@ -939,7 +1687,6 @@ describe("Gateway", () => {
     ]);
 
     try {
-      // call the `execute` function in the gateway program
       await gatewayProgram.methods
         .execute(
           amount,
@@ -951,14 +1698,12 @@ describe("Gateway", () => {
           nonce
         )
         .accountsPartial({
-          // mandatory predefined accounts
           signer: wallet.publicKey,
           pda: pdaAccount,
           destinationProgram: connectedProgram.programId,
           destinationProgramPda: connectedPdaAccount,
         })
         .remainingAccounts([
-          // accounts coming from withdraw and call msg
           { pubkey: connectedPdaAccount, isSigner: false, isWritable: true },
           { pubkey: pdaAccount, isSigner: false, isWritable: false },
           { pubkey: randomWallet.publicKey, isSigner: false, isWritable: true },
@@ -967,46 +1712,69 @@ describe("Gateway", () => {
             isSigner: false,
             isWritable: false,
           },
-          {
-            pubkey: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
-            isSigner: false,
-            isWritable: false,
-          },
+          { pubkey: otherCaller, isSigner: false, isWritable: false },
         ])
         .rpc();
-      throw new Error("Expected error not thrown"); // This line will make the test fail if no error is thrown
+      throw new Error("Expected error not thrown");
     } catch (err) {
       expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("AccountNotSigner");
     }
   });
 
-  it("Calls execute and onCall reverts if wrong msg hash", async () => {
+  it("Calls execute v2 and onCall with accounts bound to the message hash", async () => {
     await gatewayProgram.methods
       .deposit(new anchor.BN(1_000_000_000), Array.from(address), revertOptions)
       .rpc();
 
     const randomWallet = anchor.web3.Keypair.generate();
-    const lastMessageData = "revert";
+    const substitutedWallet = anchor.web3.Keypair.generate();
+    const lastMessageData = "execute_sol_v2";
     const data = Buffer.from(lastMessageData, "utf-8");
-    let seeds = [Buffer.from("connected", "utf-8")];
     const [connectedPdaAccount] = anchor.web3.PublicKey.findProgramAddressSync(
-      seeds,
+      [Buffer.from("connected", "utf-8")],
       connectedProgram.programId
     );
     const amount = new anchor.BN(500000000);
-
-    // signature
+    const forwardedAccounts = (forwardedWallet: anchor.web3.PublicKey) => [
+      { pubkey: connectedPdaAccount, isSigner: false, isWritable: true },
+      { pubkey: pdaAccount, isSigner: false, isWritable: false },
+      { pubkey: forwardedWallet, isSigner: false, isWritable: true },
+      {
+        pubkey: anchor.web3.SystemProgram.programId,
+        isSigner: false,
+        isWritable: false,
+      },
+      {
+        pubkey: connectedCaller,
+        isSigner: false,
+        isWritable: false,
+      },
+    ];
+
+    // signature committing to the forwarded accounts
+    const accountsCommitment = keccak256(
+      Buffer.concat(
+        forwardedAccounts(randomWallet.publicKey).map((account) =>
+          Buffer.concat([
+            account.pubkey.toBuffer(),
+            Buffer.from([account.isWritable ? 1 : 0]),
+          ])
+        )
+      )
+    );
     const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
     const nonce = pdaAccountData.nonce;
     const buffer = Buffer.concat([
       Buffer.from("ZETACHAIN", "utf-8"),
-      Buffer.from([0x05]),
+      Buffer.from([12]),
       chain_id_bn.toArrayLike(Buffer, "be", 8),
-      nonce.subn(1).toArrayLike(Buffer, "be", 8), // wrong nonce
+      nonce.toArrayLike(Buffer, "be", 8),
       amount.toArrayLike(Buffer, "be", 8),
       connectedProgram.programId.toBuffer(),
       Buffer.from(Array.from(address)),
       data,
+      accountsCommitment,
     ]);
     const message_hash = keccak256(buffer);
     const signature = keyPair.sign(message_hash, "hex");
@@ -1015,10 +1783,181 @@ describe("Gateway", () => {
       r.toArrayLike(Buffer, "be", 32),
       s.toArrayLike(Buffer, "be", 32),
     ]);
-
-    try {
-      // call the `execute` function in the gateway program
-      await gatewayProgram.methods
+    const executeV2 = (forwardedWallet: anchor.web3.PublicKey) =>
+      gatewayProgram.methods
+        .executeV2(
+          amount,
+          Array.from(address),
+          data,
+          Array.from(signatureBuffer),
+          Number(recoveryParam),
+          Array.from(message_hash),
+          nonce
+        )
+        .accountsPartial({
+          signer: wallet.publicKey,
+          pda: pdaAccount,
+          destinationProgram: connectedProgram.programId,
+          destinationProgramPda: connectedPdaAccount,
+        })
+        .remainingAccounts(forwardedAccounts(forwardedWallet));
+
+    // a relayer substituting a forwarded account is rejected
+    try {
+      await executeV2(substitutedWallet.publicKey).rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("MessageHashMismatch");
+    }
+
+    const randomWalletBalanceBefore = await conn.getBalance(
+      randomWallet.publicKey
+    );
+    const txsig = await executeV2(randomWallet.publicKey).rpc({
+      commitment: "confirmed",
+    });
+
+    // the data returned by on_call is emitted after the execution
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    expect(events.length).to.eq(2);
+    expect(events[0].name).to.eq("executed");
+    expect(events[1].name).to.eq("executeCompleted");
+    expect(events[1].data.revert).to.be.false;
+    expect(events[1].data.truncated).to.be.false;
+    expect(Buffer.from(events[1].data.returnData).toString("utf-8")).to.eq(
+      lastMessageData
+    );
+
+    const connectedPdaAfter = await connectedProgram.account.pda.fetch(
+      connectedPdaAccount
+    );
+    expect(connectedPdaAfter.lastMessage).to.be.eq(lastMessageData);
+    const randomWalletBalanceAfter = await conn.getBalance(
+      randomWallet.publicKey
+    );
+    expect(randomWalletBalanceBefore + amount.toNumber() / 2).to.eq(
+      randomWalletBalanceAfter
+    );
+  });
+
+  it("Calls execute and onCall reverts if connected program reverts", async () => {
+    await gatewayProgram.methods
+      .deposit(new anchor.BN(1_000_000_000), Array.from(address), revertOptions)
+      .rpc();
+
+    const randomWallet = anchor.web3.Keypair.generate();
+    const lastMessageData = "revert";
+    const data = Buffer.from(lastMessageData, "utf-8");
+    let seeds = [Buffer.from("connected", "utf-8")];
+    const [connectedPdaAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      seeds,
+      connectedProgram.programId
+    );
+    const amount = new anchor.BN(500000000);
+
+    // signature
+    const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
+    const nonce = pdaAccountData.nonce;
+    const buffer = Buffer.concat([
+      Buffer.from("ZETACHAIN", "utf-8"),
+      Buffer.from([0x05]),
+      chain_id_bn.toArrayLike(Buffer, "be", 8),
+      nonce.toArrayLike(Buffer, "be", 8),
+      amount.toArrayLike(Buffer, "be", 8),
+      connectedProgram.programId.toBuffer(),
+      Buffer.from(Array.from(address)),
+      data,
+    ]);
+    const message_hash = keccak256(buffer);
+    const signature = keyPair.sign(message_hash, "hex");
+    const { r, s, recoveryParam } = signature;
+    const signatureBuffer = Buffer.concat([
+      r.toArrayLike(Buffer, "be", 32),
+      s.toArrayLike(Buffer, "be", 32),
+    ]);
+
+    try {
+      // call the `execute` function in the gateway program
+      await gatewayProgram.methods
+        .execute(
+          amount,
+          Array.from(address),
+          data,
+          Array.from(signatureBuffer),
+          Number(recoveryParam),
+          Array.from(message_hash),
+          nonce
+        )
+        .accountsPartial({
+          // mandatory predefined accounts
+          signer: wallet.publicKey,
+          pda: pdaAccount,
+          destinationProgram: connectedProgram.programId,
+          destinationProgramPda: connectedPdaAccount,
+        })
+        .remainingAccounts([
+          // accounts coming from withdraw and call msg
+          { pubkey: connectedPdaAccount, isSigner: false, isWritable: true },
+          { pubkey: pdaAccount, isSigner: false, isWritable: false },
+          { pubkey: randomWallet.publicKey, isSigner: false, isWritable: true },
+          {
+            pubkey: anchor.web3.SystemProgram.programId,
+            isSigner: false,
+            isWritable: false,
+          },
+          {
+            pubkey: connectedCaller,
+            isSigner: false,
+            isWritable: false,
+          },
+        ])
+        .rpc();
+      throw new Error("Expected error not thrown"); // This line will make the test fail if no error is thrown
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+    }
+  });
+
+  it("Calls execute and onCall reverts if wrong msg hash", async () => {
+    await gatewayProgram.methods
+      .deposit(new anchor.BN(1_000_000_000), Array.from(address), revertOptions)
+      .rpc();
+
+    const randomWallet = anchor.web3.Keypair.generate();
+    const lastMessageData = "revert";
+    const data = Buffer.from(lastMessageData, "utf-8");
+    let seeds = [Buffer.from("connected", "utf-8")];
+    const [connectedPdaAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      seeds,
+      connectedProgram.programId
+    );
+    const amount = new anchor.BN(500000000);
+
+    // signature
+    const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
+    const nonce = pdaAccountData.nonce;
+    const buffer = Buffer.concat([
+      Buffer.from("ZETACHAIN", "utf-8"),
+      Buffer.from([0x05]),
+      chain_id_bn.toArrayLike(Buffer, "be", 8),
+      nonce.subn(1).toArrayLike(Buffer, "be", 8), // wrong nonce
+      amount.toArrayLike(Buffer, "be", 8),
+      connectedProgram.programId.toBuffer(),
+      Buffer.from(Array.from(address)),
+      data,
+    ]);
+    const message_hash = keccak256(buffer);
+    const signature = keyPair.sign(message_hash, "hex");
+    const { r, s, recoveryParam } = signature;
+    const signatureBuffer = Buffer.concat([
+      r.toArrayLike(Buffer, "be", 32),
+      s.toArrayLike(Buffer, "be", 32),
+    ]);
+
+    try {
+      // call the `execute` function in the gateway program
+      await gatewayProgram.methods
         .execute(
           amount,
           Array.from(address),
@@ -1046,7 +1985,7 @@ describe("Gateway", () => {
             isWritable: false,
           },
           {
-            pubkey: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            pubkey: connectedCaller,
             isSigner: false,
             isWritable: false,
           },
@@ -1126,7 +2065,7 @@ describe("Gateway", () => {
             isWritable: false,
           },
           {
-            pubkey: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            pubkey: connectedCaller,
             isSigner: false,
             isWritable: false,
           },
@@ -1206,7 +2145,7 @@ describe("Gateway", () => {
             isWritable: false,
           },
           {
-            pubkey: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            pubkey: connectedCaller,
             isSigner: false,
             isWritable: false,
           },
@@ -1953,15 +2892,8 @@ describe("Gateway", () => {
     }
   });
 
-  it("Calls execute spl token and onCall reverts if wrong msg hash", async () => {
+  it("Calls execute spl token and onCall forwards gateway token accounts as read-only", async () => {
     const randomWallet = anchor.web3.Keypair.generate();
-    let randomWalletAta = await spl.getOrCreateAssociatedTokenAccount(
-      conn,
-      wallet,
-      mint.publicKey,
-      randomWallet.publicKey,
-      true
-    );
     const lastMessageData = "revert";
     const data = Buffer.from(lastMessageData, "utf-8");
     let seeds = [Buffer.from("connected", "utf-8")];
@@ -1990,7 +2922,7 @@ describe("Gateway", () => {
       Buffer.from("ZETACHAIN", "utf-8"),
       Buffer.from([0x06]),
       chain_id_bn.toArrayLike(Buffer, "be", 8),
-      nonce.subn(1).toArrayLike(Buffer, "be", 8), // wrong nonce
+      nonce.toArrayLike(Buffer, "be", 8),
       amount.toArrayLike(Buffer, "be", 8),
       mint.publicKey.toBuffer(),
       destinationPdaAta.address.toBuffer(),
@@ -2046,11 +2978,8 @@ describe("Gateway", () => {
             isSigner: false,
             isWritable: false,
           },
-          {
-            pubkey: randomWalletAta.address,
-            isSigner: false,
-            isWritable: true,
-          },
+          // gateway ATA requested as writable in place of the random wallet ATA
+          { pubkey: pda_ata, isSigner: false, isWritable: true },
           {
             pubkey: spl.TOKEN_PROGRAM_ID,
             isSigner: false,
@@ -2065,13 +2994,12 @@ describe("Gateway", () => {
         .rpc();
       throw new Error("Expected error not thrown"); // This line will make the test fail if no error is thrown
     } catch (err) {
-      expect(err).to.be.instanceof(anchor.AnchorError);
-      expect(err.message).to.include("MessageHashMismatch");
+      // the destination program receives the gateway ATA as read-only
+      expect(err.logs.join("\n")).to.include("ConstraintMut");
     }
   });
 
-  it("Calls execute spl token and onCall reverts if wrong signer", async () => {
-    const key = ec.genKeyPair(); // non TSS key pair
+  it("Calls execute spl token and onCall reverts if wrong msg hash", async () => {
     const randomWallet = anchor.web3.Keypair.generate();
     let randomWalletAta = await spl.getOrCreateAssociatedTokenAccount(
       conn,
@@ -2108,7 +3036,7 @@ describe("Gateway", () => {
       Buffer.from("ZETACHAIN", "utf-8"),
       Buffer.from([0x06]),
       chain_id_bn.toArrayLike(Buffer, "be", 8),
-      nonce.toArrayLike(Buffer, "be", 8),
+      nonce.subn(1).toArrayLike(Buffer, "be", 8), // wrong nonce
       amount.toArrayLike(Buffer, "be", 8),
       mint.publicKey.toBuffer(),
       destinationPdaAta.address.toBuffer(),
@@ -2116,7 +3044,7 @@ describe("Gateway", () => {
       data,
     ]);
     const message_hash = keccak256(buffer);
-    const signature = key.sign(message_hash, "hex");
+    const signature = keyPair.sign(message_hash, "hex");
     const { r, s, recoveryParam } = signature;
     const signatureBuffer = Buffer.concat([
       r.toArrayLike(Buffer, "be", 32),
@@ -2184,13 +3112,21 @@ describe("Gateway", () => {
       throw new Error("Expected error not thrown"); // This line will make the test fail if no error is thrown
     } catch (err) {
       expect(err).to.be.instanceof(anchor.AnchorError);
-      expect(err.message).to.include("TSSAuthenticationFailed");
+      expect(err.message).to.include("MessageHashMismatch");
     }
   });
 
-  it("Calls execute spl token and onRevert", async () => {
+  it("Calls execute spl token and onCall reverts if wrong signer", async () => {
+    const key = ec.genKeyPair(); // non TSS key pair
     const randomWallet = anchor.web3.Keypair.generate();
-    const lastMessageData = "execute_spl";
+    let randomWalletAta = await spl.getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      mint.publicKey,
+      randomWallet.publicKey,
+      true
+    );
+    const lastMessageData = "revert";
     const data = Buffer.from(lastMessageData, "utf-8");
     let seeds = [Buffer.from("connected", "utf-8")];
     const [connectedPdaAccount] = anchor.web3.PublicKey.findProgramAddressSync(
@@ -2216,80 +3152,190 @@ describe("Gateway", () => {
 
     const buffer = Buffer.concat([
       Buffer.from("ZETACHAIN", "utf-8"),
-      Buffer.from([0x09]),
+      Buffer.from([0x06]),
       chain_id_bn.toArrayLike(Buffer, "be", 8),
       nonce.toArrayLike(Buffer, "be", 8),
       amount.toArrayLike(Buffer, "be", 8),
       mint.publicKey.toBuffer(),
       destinationPdaAta.address.toBuffer(),
-      randomWallet.publicKey.toBuffer(),
+      Buffer.from(Array.from(address)),
       data,
     ]);
     const message_hash = keccak256(buffer);
-    const signature = keyPair.sign(message_hash, "hex");
+    const signature = key.sign(message_hash, "hex");
     const { r, s, recoveryParam } = signature;
     const signatureBuffer = Buffer.concat([
       r.toArrayLike(Buffer, "be", 32),
       s.toArrayLike(Buffer, "be", 32),
     ]);
 
-    // balances before
-    const destinationPdaAtaAccBefore = await spl.getAccount(
-      conn,
-      destinationPdaAta.address
-    );
-
-    // call the `execute_spl_token_revert` function in the gateway program
-    await gatewayProgram.methods
-      .executeSplTokenRevert(
-        usdcDecimals,
-        amount,
-        randomWallet.publicKey,
-        data,
-        Array.from(signatureBuffer),
-        Number(recoveryParam),
-        Array.from(message_hash),
-        nonce
-      )
-      .accountsPartial({
-        // mandatory predefined accounts
-        signer: wallet.publicKey,
-        pda: pdaAccount,
-        pdaAta: pda_ata,
-        mintAccount: mint.publicKey,
-        destinationProgram: connectedSPLProgram.programId,
-        destinationProgramPda: connectedPdaAccount,
-        destinationProgramPdaAta: destinationPdaAta.address,
-        tokenProgram: spl.TOKEN_PROGRAM_ID,
-        associatedTokenProgram: spl.ASSOCIATED_TOKEN_PROGRAM_ID,
-        systemProgram: SYSTEM_PROGRAM_ID,
-      })
-      .remainingAccounts([
-        // accounts coming from revert msg
-        { pubkey: connectedPdaAccount, isSigner: false, isWritable: true },
-        {
-          pubkey: destinationPdaAta.address,
-          isSigner: false,
-          isWritable: true,
-        },
-        { pubkey: mint.publicKey, isSigner: false, isWritable: false },
-        { pubkey: pdaAccount, isSigner: false, isWritable: false },
-        {
-          pubkey: spl.TOKEN_PROGRAM_ID,
-          isSigner: false,
-          isWritable: false,
-        },
-        {
-          pubkey: SYSTEM_PROGRAM_ID,
-          isSigner: false,
-          isWritable: false,
-        },
-      ])
-      .rpc();
-
-    const connectedPdaAfter = await connectedSPLProgram.account.pda.fetch(
-      connectedPdaAccount
-    );
+    try {
+      // call the `execute_spl_token` function in the gateway program
+      await gatewayProgram.methods
+        .executeSplToken(
+          usdcDecimals,
+          amount,
+          Array.from(address),
+          data,
+          Array.from(signatureBuffer),
+          Number(recoveryParam),
+          Array.from(message_hash),
+          nonce
+        )
+        .accountsPartial({
+          // mandatory predefined accounts
+          signer: wallet.publicKey,
+          pda: pdaAccount,
+          pdaAta: pda_ata,
+          mintAccount: mint.publicKey,
+          destinationProgram: connectedSPLProgram.programId,
+          destinationProgramPda: connectedPdaAccount,
+          destinationProgramPdaAta: destinationPdaAta.address,
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: spl.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SYSTEM_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          // accounts coming from withdraw and call msg
+          { pubkey: connectedPdaAccount, isSigner: false, isWritable: true },
+          {
+            pubkey: destinationPdaAta.address,
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: mint.publicKey, isSigner: false, isWritable: false },
+          { pubkey: pdaAccount, isSigner: false, isWritable: false },
+          {
+            pubkey: randomWallet.publicKey,
+            isSigner: false,
+            isWritable: false,
+          },
+          {
+            pubkey: randomWalletAta.address,
+            isSigner: false,
+            isWritable: true,
+          },
+          {
+            pubkey: spl.TOKEN_PROGRAM_ID,
+            isSigner: false,
+            isWritable: false,
+          },
+          {
+            pubkey: SYSTEM_PROGRAM_ID,
+            isSigner: false,
+            isWritable: false,
+          },
+        ])
+        .rpc();
+      throw new Error("Expected error not thrown"); // This line will make the test fail if no error is thrown
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("TSSAuthenticationFailed");
+    }
+  });
+
+  it("Calls execute spl token and onRevert", async () => {
+    const randomWallet = anchor.web3.Keypair.generate();
+    const lastMessageData = "execute_spl";
+    const data = Buffer.from(lastMessageData, "utf-8");
+    let seeds = [Buffer.from("connected", "utf-8")];
+    const [connectedPdaAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      seeds,
+      connectedSPLProgram.programId
+    );
+    let pda_ata = await spl.getAssociatedTokenAddress(
+      mint.publicKey,
+      pdaAccount,
+      true
+    );
+    const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
+    const amount = new anchor.BN(500_000);
+    const nonce = pdaAccountData.nonce;
+
+    let destinationPdaAta = await spl.getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      mint.publicKey,
+      connectedPdaAccount,
+      true
+    );
+
+    const buffer = Buffer.concat([
+      Buffer.from("ZETACHAIN", "utf-8"),
+      Buffer.from([0x09]),
+      chain_id_bn.toArrayLike(Buffer, "be", 8),
+      nonce.toArrayLike(Buffer, "be", 8),
+      amount.toArrayLike(Buffer, "be", 8),
+      mint.publicKey.toBuffer(),
+      destinationPdaAta.address.toBuffer(),
+      randomWallet.publicKey.toBuffer(),
+      data,
+    ]);
+    const message_hash = keccak256(buffer);
+    const signature = keyPair.sign(message_hash, "hex");
+    const { r, s, recoveryParam } = signature;
+    const signatureBuffer = Buffer.concat([
+      r.toArrayLike(Buffer, "be", 32),
+      s.toArrayLike(Buffer, "be", 32),
+    ]);
+
+    // balances before
+    const destinationPdaAtaAccBefore = await spl.getAccount(
+      conn,
+      destinationPdaAta.address
+    );
+
+    // call the `execute_spl_token_revert` function in the gateway program
+    await gatewayProgram.methods
+      .executeSplTokenRevert(
+        usdcDecimals,
+        amount,
+        randomWallet.publicKey,
+        data,
+        Array.from(signatureBuffer),
+        Number(recoveryParam),
+        Array.from(message_hash),
+        nonce
+      )
+      .accountsPartial({
+        // mandatory predefined accounts
+        signer: wallet.publicKey,
+        pda: pdaAccount,
+        pdaAta: pda_ata,
+        mintAccount: mint.publicKey,
+        destinationProgram: connectedSPLProgram.programId,
+        destinationProgramPda: connectedPdaAccount,
+        destinationProgramPdaAta: destinationPdaAta.address,
+        tokenProgram: spl.TOKEN_PROGRAM_ID,
+        associatedTokenProgram: spl.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        // accounts coming from revert msg
+        { pubkey: connectedPdaAccount, isSigner: false, isWritable: true },
+        {
+          pubkey: destinationPdaAta.address,
+          isSigner: false,
+          isWritable: true,
+        },
+        { pubkey: mint.publicKey, isSigner: false, isWritable: false },
+        { pubkey: pdaAccount, isSigner: false, isWritable: false },
+        {
+          pubkey: spl.TOKEN_PROGRAM_ID,
+          isSigner: false,
+          isWritable: false,
+        },
+        {
+          pubkey: SYSTEM_PROGRAM_ID,
+          isSigner: false,
+          isWritable: false,
+        },
+      ])
+      .rpc();
+
+    const connectedPdaAfter = await connectedSPLProgram.account.pda.fetch(
+      connectedPdaAccount
+    );
 
     // check connected pda state was updated
     expect(connectedPdaAfter.lastRevertMessage).to.be.eq(lastMessageData);
@@ -2818,6 +3864,7 @@ describe("Gateway", () => {
           nonce
         )
         .accounts({
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
           pdaAta: pda_ata,
           mintAccount: mint.publicKey,
           recipientAta: to,
@@ -2874,6 +3921,7 @@ describe("Gateway", () => {
           nonce
         )
         .accounts({
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
           pdaAta: pda_ata,
           mintAccount: mint.publicKey,
           recipientAta: to,
@@ -2917,128 +3965,972 @@ describe("Gateway", () => {
       throw new Error("Expected error not thrown");
     } catch (err) {
       expect(err).to.be.instanceof(anchor.AnchorError);
-      expect(err.message).to.include("MemoLengthExceeded");
+      expect(err.message).to.include("MemoLengthExceeded");
+    }
+  });
+
+  it("Call with empty address receiver should fail", async () => {
+    try {
+      await gatewayProgram.methods
+        .call(Array(20).fill(0), Buffer.from("hello", "utf-8"), revertOptions)
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("EmptyReceiver");
+    }
+  });
+
+  it("Call with above max payload size should fail", async () => {
+    try {
+      await gatewayProgram.methods
+        .call(
+          Array.from(address),
+          Buffer.from(Array(maxPayloadSize + 1).fill(1)),
+          revertOptions
+        )
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("MemoLengthExceeded");
+    }
+  });
+
+  it("Call with max payload size", async () => {
+    const txsig = await gatewayProgram.methods
+      .call(
+        Array.from(address),
+        Buffer.from(Array(maxPayloadSize).fill(1)),
+        revertOptions
+      )
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 }),
+      ])
+      .rpc({ commitment: "processed" });
+    await conn.getParsedTransaction(txsig, "confirmed");
+  });
+
+  it("Deposit and call with max payload size", async () => {
+    const bal1 = await conn.getBalance(pdaAccount);
+    const txsig = await gatewayProgram.methods
+      .depositAndCall(
+        new anchor.BN(1_000_000_000),
+        Array.from(address),
+        Buffer.from(Array(maxPayloadSize).fill(1)),
+        revertOptions
+      )
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 }),
+      ])
+      .rpc({ commitment: "processed" });
+    await conn.getParsedTransaction(txsig, "confirmed");
+    const bal2 = await conn.getBalance(pdaAccount);
+    expect(bal2 - bal1).to.be.gte(1_000_000_000);
+  });
+
+  it("Deposit and call", async () => {
+    let bal1 = await conn.getBalance(pdaAccount);
+    const txsig = await gatewayProgram.methods
+      .depositAndCall(
+        new anchor.BN(1_000_000_000),
+        Array.from(address),
+        Buffer.from("hello", "utf-8"),
+        revertOptions
+      )
+      .rpc({ commitment: "processed" });
+    await conn.getParsedTransaction(txsig, "confirmed");
+    let bal2 = await conn.getBalance(pdaAccount);
+    expect(bal2 - bal1).to.be.gte(1_000_000_000);
+  });
+
+  it("Deposit below the minimum deposit should fail", async () => {
+    await gatewayProgram.methods
+      .updateMinDeposit(new anchor.BN(2_000_000))
+      .rpc();
+
+    try {
+      await gatewayProgram.methods
+        .deposit(new anchor.BN(1_000_000), Array.from(address), revertOptions)
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("DepositBelowMinimum");
+      expect(err.logs.join("\n")).to.include(
+        "Deposit amount 1000000 is below the minimum deposit of 2000000"
+      );
+    }
+
+    await gatewayProgram.methods.updateMinDeposit(new anchor.BN(0)).rpc();
+
+    try {
+      await gatewayProgram.methods
+        .deposit(new anchor.BN(0), Array.from(address), revertOptions)
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("DepositBelowMinimum");
+    }
+  });
+
+  it("Deposit emits Deposited event", async () => {
+    const txsig = await gatewayProgram.methods
+      .deposit(new anchor.BN(1_000_000), Array.from(address), revertOptions)
+      .rpc({ commitment: "confirmed" });
+
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    expect(events.length).to.eq(1);
+    expect(events[0].name).to.eq("deposited");
+    expect(events[0].data.sender.toBase58()).to.eq(
+      wallet.publicKey.toBase58()
+    );
+    expect(Array.from(events[0].data.receiver)).to.deep.eq(
+      Array.from(address)
+    );
+    expect(events[0].data.amount.toNumber()).to.eq(1_000_000);
+    expect(events[0].data.fee.toNumber()).to.eq(2_000_000);
+    expect(events[0].data.mint).to.be.null;
+    expect(events[0].data.revertOptions.revertAddress.toBase58()).to.eq(
+      revertOptions.revertAddress.toBase58()
+    );
+  });
+
+  it("Deposit v2 to a Bitcoin receiver emits DepositedV2 event", async () => {
+    const receiver = {
+      addressType: { bitcoin: {} },
+      address: Buffer.from("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"),
+      chainId: new anchor.BN(8332),
+    };
+    const txsig = await gatewayProgram.methods
+      .depositV2(new anchor.BN(1_000_000), receiver, null)
+      .rpc({ commitment: "confirmed" });
+
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    expect(events.length).to.eq(1);
+    expect(events[0].name).to.eq("depositedV2");
+    expect(events[0].data.receiver.addressType).to.deep.eq({ bitcoin: {} });
+    expect(Buffer.from(events[0].data.receiver.address).toString()).to.eq(
+      "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"
+    );
+    expect(events[0].data.receiver.chainId.toNumber()).to.eq(8332);
+    expect(events[0].data.amount.toNumber()).to.eq(1_000_000);
+  });
+
+  it("Call v2 with a receiver not matching its address type should fail", async () => {
+    try {
+      await gatewayProgram.methods
+        .callV2(
+          {
+            addressType: { evm: {} },
+            address: wallet.publicKey.toBuffer(),
+            chainId: new anchor.BN(7000),
+          },
+          Buffer.from("hello", "utf-8"),
+          null
+        )
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("InvalidReceiver");
+    }
+
+    await gatewayProgram.methods
+      .callV2(
+        {
+          addressType: { solana: {} },
+          address: wallet.publicKey.toBuffer(),
+          chainId: new anchor.BN(900),
+        },
+        Buffer.from("hello", "utf-8"),
+        null
+      )
+      .rpc();
+  });
+
+  it("Deposit with swap credits the swap output and fails on slippage", async () => {
+    // Pool swapping a fresh input token into whitelisted USDC at 2:1
+    const inputMint = await spl.createMint(
+      conn,
+      wallet,
+      wallet.publicKey,
+      null,
+      usdcDecimals
+    );
+    const [pool] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), inputMint.toBuffer(), mint.publicKey.toBuffer()],
+      mockAmmProgram.programId
+    );
+    await mockAmmProgram.methods
+      .initializePool(new anchor.BN(1), new anchor.BN(2))
+      .accounts({ inputMint, outputMint: mint.publicKey })
+      .rpc();
+    const poolInputVault = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      inputMint,
+      pool,
+      true
+    );
+    const poolOutputVault = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      mint.publicKey,
+      pool,
+      true
+    );
+    await spl.mintTo(
+      conn,
+      wallet,
+      mint.publicKey,
+      poolOutputVault.address,
+      wallet,
+      10_000_000
+    );
+    const source = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      inputMint,
+      wallet.publicKey
+    );
+    await spl.mintTo(conn, wallet, inputMint, source.address, wallet, 10_000_000);
+    const pda_ata = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      mint.publicKey,
+      pdaAccount,
+      true
+    );
+
+    await gatewayProgram.methods
+      .updateSwapProgram(mockAmmProgram.programId)
+      .rpc();
+
+    const swapAccounts = [
+      { pubkey: wallet.publicKey, isSigner: true, isWritable: false },
+      { pubkey: pool, isSigner: false, isWritable: false },
+      { pubkey: inputMint, isSigner: false, isWritable: false },
+      { pubkey: mint.publicKey, isSigner: false, isWritable: false },
+      { pubkey: source.address, isSigner: false, isWritable: true },
+      { pubkey: pda_ata.address, isSigner: false, isWritable: true },
+      { pubkey: poolInputVault.address, isSigner: false, isWritable: true },
+      { pubkey: poolOutputVault.address, isSigner: false, isWritable: true },
+      { pubkey: spl.TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    ];
+    const depositWithSwap = (amountIn: number, minimumAmountOut: number) =>
+      gatewayProgram.methods
+        .depositWithSwap(
+          new anchor.BN(amountIn),
+          new anchor.BN(minimumAmountOut),
+          mockAmmProgram.coder.instruction.encode("swap", {
+            amountIn: new anchor.BN(amountIn),
+            minimumAmountOut: new anchor.BN(0),
+          }),
+          Array.from(address),
+          revertOptions
+        )
+        .accounts({
+          mintAccount: mint.publicKey,
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
+          source: source.address,
+          to: pda_ata.address,
+          swapProgram: mockAmmProgram.programId,
+        })
+        .remainingAccounts(swapAccounts);
+
+    const txsig = await depositWithSwap(2_000_000, 1_000_000).rpc({
+      commitment: "confirmed",
+    });
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    expect(events.length).to.eq(2);
+    expect(events[0].name).to.eq("swappedForDeposit");
+    expect(events[0].data.inputMint.toBase58()).to.eq(inputMint.toBase58());
+    expect(events[0].data.amountIn.toNumber()).to.eq(2_000_000);
+    expect(events[0].data.amountOut.toNumber()).to.eq(1_000_000);
+    expect(events[1].name).to.eq("deposited");
+    expect(events[1].data.amount.toNumber()).to.eq(1_000_000);
+    expect(events[1].data.mint.toBase58()).to.eq(mint.publicKey.toBase58());
+
+    try {
+      await depositWithSwap(2_000_000, 1_000_001).rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("SlippageExceeded");
+    }
+
+    // a swap depositing into the gateway again would have the nested deposit counted twice
+    const [eventAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority", "utf-8")],
+      gatewayProgram.programId
+    );
+    try {
+      await gatewayProgram.methods
+        .depositWithSwap(
+          new anchor.BN(2_000_000),
+          new anchor.BN(1_000_000),
+          mockAmmProgram.coder.instruction.encode("swapAndDeposit", {
+            amountIn: new anchor.BN(2_000_000),
+            minimumAmountOut: new anchor.BN(0),
+            depositAmount: new anchor.BN(1_000_000),
+          }),
+          Array.from(address),
+          revertOptions
+        )
+        .accounts({
+          mintAccount: mint.publicKey,
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
+          source: source.address,
+          to: pda_ata.address,
+          swapProgram: mockAmmProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
+          ...swapAccounts.slice(1),
+          { pubkey: pdaAccount, isSigner: false, isWritable: true },
+          { pubkey: eventAuthority, isSigner: false, isWritable: false },
+          {
+            pubkey: gatewayProgram.programId,
+            isSigner: false,
+            isWritable: false,
+          },
+          {
+            pubkey: anchor.web3.SystemProgram.programId,
+            isSigner: false,
+            isWritable: false,
+          },
+        ])
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("InvalidSwapAccounts");
+    }
+  });
+
+  it("Deposit SOL with swap credits the lamports received by the PDA", async () => {
+    // Pool swapping a fresh input token into SOL at 2:1, paying out of its own lamports
+    const inputMint = await spl.createMint(
+      conn,
+      wallet,
+      wallet.publicKey,
+      null,
+      usdcDecimals
+    );
+    const [pool] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), inputMint.toBuffer(), spl.NATIVE_MINT.toBuffer()],
+      mockAmmProgram.programId
+    );
+    await mockAmmProgram.methods
+      .initializePool(new anchor.BN(1), new anchor.BN(2))
+      .accounts({ inputMint, outputMint: spl.NATIVE_MINT })
+      .rpc();
+    await anchor.web3.sendAndConfirmTransaction(
+      conn,
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: wallet.publicKey,
+          toPubkey: pool,
+          lamports: 100_000_000,
+        })
+      ),
+      [wallet]
+    );
+    const poolInputVault = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      inputMint,
+      pool,
+      true
+    );
+    const source = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      inputMint,
+      wallet.publicKey
+    );
+    await spl.mintTo(conn, wallet, inputMint, source.address, wallet, 100_000_000);
+
+    await gatewayProgram.methods
+      .updateSwapProgram(mockAmmProgram.programId)
+      .rpc();
+
+    const swapAccounts = [
+      { pubkey: wallet.publicKey, isSigner: true, isWritable: false },
+      { pubkey: pool, isSigner: false, isWritable: true },
+      { pubkey: inputMint, isSigner: false, isWritable: false },
+      { pubkey: source.address, isSigner: false, isWritable: true },
+      { pubkey: poolInputVault.address, isSigner: false, isWritable: true },
+      { pubkey: pdaAccount, isSigner: false, isWritable: true },
+      { pubkey: spl.TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    ];
+    const depositSolWithSwap = (amountIn: number, minimumAmountOut: number) =>
+      gatewayProgram.methods
+        .depositSolWithSwap(
+          new anchor.BN(amountIn),
+          new anchor.BN(minimumAmountOut),
+          mockAmmProgram.coder.instruction.encode("swapToSol", {
+            amountIn: new anchor.BN(amountIn),
+            minimumAmountOut: new anchor.BN(0),
+          }),
+          Array.from(address),
+          revertOptions
+        )
+        .accounts({
+          source: source.address,
+          swapProgram: mockAmmProgram.programId,
+        })
+        .remainingAccounts(swapAccounts);
+
+    const balanceBefore = await conn.getBalance(pdaAccount);
+    const txsig = await depositSolWithSwap(20_000_000, 10_000_000).rpc({
+      commitment: "confirmed",
+    });
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    expect(events.length).to.eq(2);
+    expect(events[0].name).to.eq("swappedForDeposit");
+    expect(events[0].data.inputMint.toBase58()).to.eq(inputMint.toBase58());
+    expect(events[0].data.amountIn.toNumber()).to.eq(20_000_000);
+    expect(events[0].data.outputMint).to.be.null;
+    expect(events[0].data.amountOut.toNumber()).to.eq(10_000_000);
+    expect(events[1].name).to.eq("deposited");
+    expect(events[1].data.amount.toNumber()).to.eq(10_000_000);
+    expect(events[1].data.mint).to.be.null;
+    const balanceAfter = await conn.getBalance(pdaAccount, "confirmed");
+    expect(balanceAfter - balanceBefore).to.eq(
+      10_000_000 + events[1].data.fee.toNumber()
+    );
+
+    try {
+      await depositSolWithSwap(20_000_000, 10_000_001).rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("SlippageExceeded");
+    }
+  });
+
+  it("Deposit multi transfers SOL and SPL tokens with a single fee", async () => {
+    const pda_ata = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      mint.publicKey,
+      pdaAccount,
+      true
+    );
+    const tokenAccount = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      mint.publicKey,
+      wallet.publicKey
+    );
+    const [entryAddress] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("whitelist", "utf-8"), mint.publicKey.toBuffer()],
+      gatewayProgram.programId
+    );
+    const assetAccounts = [
+      { pubkey: entryAddress, isSigner: false, isWritable: false },
+      { pubkey: mint.publicKey, isSigner: false, isWritable: false },
+      { pubkey: tokenAccount.address, isSigner: false, isWritable: true },
+      { pubkey: pda_ata.address, isSigner: false, isWritable: true },
+      { pubkey: spl.TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    ];
+    const pdaBalanceBefore = await conn.getBalance(pdaAccount);
+    const ataBalanceBefore = (await spl.getAccount(conn, pda_ata.address))
+      .amount;
+
+    const txsig = await gatewayProgram.methods
+      .depositMulti(
+        new anchor.BN(100_000_000),
+        [{ mint: mint.publicKey, amount: new anchor.BN(1_000_000) }],
+        Array.from(address),
+        Buffer.from("hello", "utf-8"),
+        revertOptions
+      )
+      .remainingAccounts(assetAccounts)
+      .rpc({ commitment: "confirmed" });
+
+    const pdaBalanceAfter = await conn.getBalance(pdaAccount);
+    const ataBalanceAfter = (await spl.getAccount(conn, pda_ata.address))
+      .amount;
+    expect(pdaBalanceAfter - pdaBalanceBefore).to.eq(100_000_000 + 2_000_000);
+    expect(Number(ataBalanceAfter - ataBalanceBefore)).to.eq(1_000_000);
+
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    expect(events.length).to.eq(1);
+    expect(events[0].name).to.eq("depositedMulti");
+    expect(events[0].data.solAmount.toNumber()).to.eq(100_000_000);
+    expect(events[0].data.assets.length).to.eq(1);
+    expect(events[0].data.assets[0].amount.toNumber()).to.eq(1_000_000);
+    expect(events[0].data.fee.toNumber()).to.eq(2_000_000);
+    expect(Buffer.from(events[0].data.message).toString()).to.eq("hello");
+
+    // The single fee is the highest fee among the deposited assets
+    await gatewayProgram.methods
+      .updateWhitelistEntry(
+        new anchor.BN(0),
+        new anchor.BN("18446744073709551615"),
+        false,
+        new anchor.BN(5_000_000)
+      )
+      .accounts({ mintAccount: mint.publicKey })
+      .rpc();
+    const feeTxsig = await gatewayProgram.methods
+      .depositMulti(
+        new anchor.BN(100_000_000),
+        [{ mint: mint.publicKey, amount: new anchor.BN(1_000_000) }],
+        Array.from(address),
+        null,
+        revertOptions
+      )
+      .remainingAccounts(assetAccounts)
+      .rpc({ commitment: "confirmed" });
+    const feeEvents = await getCpiEvents(gatewayProgram, conn, feeTxsig);
+    expect(feeEvents[0].data.fee.toNumber()).to.eq(5_000_000);
+    await gatewayProgram.methods
+      .updateWhitelistEntry(
+        new anchor.BN(0),
+        new anchor.BN("18446744073709551615"),
+        false,
+        null
+      )
+      .accounts({ mintAccount: mint.publicKey })
+      .rpc();
+
+    try {
+      await gatewayProgram.methods
+        .depositMulti(
+          new anchor.BN(0),
+          [
+            { mint: mint.publicKey, amount: new anchor.BN(1_000_000) },
+            { mint: mint.publicKey, amount: new anchor.BN(1_000_000) },
+          ],
+          Array.from(address),
+          null,
+          revertOptions
+        )
+        .remainingAccounts([...assetAccounts, ...assetAccounts])
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("InvalidDepositAssets");
+    }
+  });
+
+  it("Deposit SPL and call emits DepositedAndCalled event", async () => {
+    const pda_ata = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      mint.publicKey,
+      pdaAccount,
+      true
+    );
+    const tokenAccount = await getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      mint.publicKey,
+      wallet.publicKey
+    );
+    const txsig = await gatewayProgram.methods
+      .depositSplTokenAndCall(
+        new anchor.BN(1_000_000),
+        Array.from(address),
+        Buffer.from("hello", "utf-8"),
+        revertOptions
+      )
+      .accounts({
+        tokenProgram: spl.TOKEN_PROGRAM_ID,
+        from: tokenAccount.address,
+        to: pda_ata.address,
+        mintAccount: mint.publicKey,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    expect(events.length).to.eq(1);
+    expect(events[0].name).to.eq("depositedAndCalled");
+    expect(events[0].data.amount.toNumber()).to.eq(1_000_000);
+    expect(events[0].data.mint.toBase58()).to.eq(mint.publicKey.toBase58());
+    expect(Buffer.from(events[0].data.message).toString("utf-8")).to.eq(
+      "hello"
+    );
+  });
+
+  it("Call emits Called event", async () => {
+    const txsig = await gatewayProgram.methods
+      .call(Array.from(address), Buffer.from("hello", "utf-8"), null)
+      .rpc({ commitment: "confirmed" });
+
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    expect(events.length).to.eq(1);
+    expect(events[0].name).to.eq("called");
+    expect(events[0].data.revertOptions).to.be.null;
+  });
+
+  it("Deposit SPL with empty address receiver should fail", async () => {
+    try {
+      await depositSplTokens(
+        gatewayProgram,
+        conn,
+        wallet,
+        mint,
+        Buffer.alloc(20)
+      );
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("EmptyReceiver");
+    }
+  });
+
+  it("Unwhitelist SPL token and deposit should fail", async () => {
+    await gatewayProgram.methods
+      .unwhitelistSplMint([], 0, [], new anchor.BN(0))
+      .accounts({
+        whitelistCandidate: mint.publicKey,
+      })
+      .rpc();
+
+    try {
+      await depositSplTokens(gatewayProgram, conn, wallet, mint, address);
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("MintWithdrawOnly");
+    }
+  });
+
+  it("Re-whitelist SPL token and deposit should succeed", async () => {
+    await gatewayProgram.methods
+      .whitelistSplMint([], 0, [], new anchor.BN(0))
+      .accounts({
+        whitelistCandidate: mint.publicKey,
+      })
+      .rpc();
+    await depositSplTokens(gatewayProgram, conn, wallet, mint, address);
+  });
+
+  it("Whitelist Token-2022 mint with non-transferable extension should fail", async () => {
+    const nonTransferableMint = anchor.web3.Keypair.generate();
+    const space = spl.getMintLen([spl.ExtensionType.NonTransferable]);
+    const tx = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: wallet.publicKey,
+        newAccountPubkey: nonTransferableMint.publicKey,
+        lamports: await conn.getMinimumBalanceForRentExemption(space),
+        space,
+        programId: spl.TOKEN_2022_PROGRAM_ID,
+      }),
+      spl.createInitializeNonTransferableMintInstruction(
+        nonTransferableMint.publicKey,
+        spl.TOKEN_2022_PROGRAM_ID
+      ),
+      spl.createInitializeMintInstruction(
+        nonTransferableMint.publicKey,
+        usdcDecimals,
+        wallet.publicKey,
+        null,
+        spl.TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await anchor.web3.sendAndConfirmTransaction(conn, tx, [
+      wallet,
+      nonTransferableMint,
+    ]);
+
+    try {
+      await gatewayProgram.methods
+        .whitelistSplMint([], 0, [], new anchor.BN(0))
+        .accounts({
+          whitelistCandidate: nonTransferableMint.publicKey,
+        })
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("UnsupportedMintExtension");
+    }
+  });
+
+  it("Deposit Token-2022 with transfer fee credits the amount received", async () => {
+    const feeMint = anchor.web3.Keypair.generate();
+    const space = spl.getMintLen([spl.ExtensionType.TransferFeeConfig]);
+    const tx = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: wallet.publicKey,
+        newAccountPubkey: feeMint.publicKey,
+        lamports: await conn.getMinimumBalanceForRentExemption(space),
+        space,
+        programId: spl.TOKEN_2022_PROGRAM_ID,
+      }),
+      spl.createInitializeTransferFeeConfigInstruction(
+        feeMint.publicKey,
+        wallet.publicKey,
+        wallet.publicKey,
+        100, // 1%
+        BigInt(1_000_000),
+        spl.TOKEN_2022_PROGRAM_ID
+      ),
+      spl.createInitializeMintInstruction(
+        feeMint.publicKey,
+        usdcDecimals,
+        wallet.publicKey,
+        null,
+        spl.TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await anchor.web3.sendAndConfirmTransaction(conn, tx, [wallet, feeMint]);
+
+    await gatewayProgram.methods
+      .whitelistSplMint([], 0, [], new anchor.BN(0))
+      .accounts({
+        whitelistCandidate: feeMint.publicKey,
+      })
+      .rpc();
+
+    const from = await spl.getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      feeMint.publicKey,
+      wallet.publicKey,
+      false,
+      undefined,
+      undefined,
+      spl.TOKEN_2022_PROGRAM_ID
+    );
+    await spl.mintTo(
+      conn,
+      wallet,
+      feeMint.publicKey,
+      from.address,
+      wallet,
+      10_000_000,
+      [],
+      undefined,
+      spl.TOKEN_2022_PROGRAM_ID
+    );
+    const to = await spl.getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      feeMint.publicKey,
+      pdaAccount,
+      true,
+      undefined,
+      undefined,
+      spl.TOKEN_2022_PROGRAM_ID
+    );
+
+    const txsig = await gatewayProgram.methods
+      .depositSplToken(new anchor.BN(1_000_000), Array.from(address), null)
+      .accounts({
+        tokenProgram: spl.TOKEN_2022_PROGRAM_ID,
+        from: from.address,
+        to: to.address,
+        mintAccount: feeMint.publicKey,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const events = await getCpiEvents(gatewayProgram, conn, txsig);
+    const deposited = events.find((e) => e.name === "deposited");
+    expect(deposited.data.amount.toNumber()).to.eq(990_000);
+
+    const toAccount = await spl.getAccount(
+      conn,
+      to.address,
+      "confirmed",
+      spl.TOKEN_2022_PROGRAM_ID
+    );
+    expect(Number(toAccount.amount)).to.eq(990_000);
+
+    const fromAccount = await spl.getAccount(
+      conn,
+      from.address,
+      "confirmed",
+      spl.TOKEN_2022_PROGRAM_ID
+    );
+    expect(Number(fromAccount.amount)).to.eq(9_000_000);
+  });
+
+  it("Close mint vault sweeps the balance to the treasury once withdraw-only", async () => {
+    const vaultMint = anchor.web3.Keypair.generate();
+    await mintSPLToken(conn, wallet, vaultMint);
+    await gatewayProgram.methods
+      .whitelistSplMint([], 0, [], new anchor.BN(0))
+      .accounts({
+        whitelistCandidate: vaultMint.publicKey,
+      })
+      .rpc();
+
+    const walletAta = await spl.getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      vaultMint.publicKey,
+      wallet.publicKey
+    );
+    await spl.mintTo(
+      conn,
+      wallet,
+      vaultMint.publicKey,
+      walletAta.address,
+      wallet,
+      10_000_000
+    );
+    await depositSplTokens(gatewayProgram, conn, wallet, vaultMint, address);
+
+    const pdaAta = spl.getAssociatedTokenAddressSync(
+      vaultMint.publicKey,
+      pdaAccount,
+      true
+    );
+    const { treasury } = await gatewayProgram.account.pda.fetch(pdaAccount);
+    const treasuryAta = await spl.getOrCreateAssociatedTokenAccount(
+      conn,
+      wallet,
+      vaultMint.publicKey,
+      treasury,
+      true
+    );
+
+    try {
+      await gatewayProgram.methods
+        .closeMintVault()
+        .accounts({
+          mintAccount: vaultMint.publicKey,
+          treasuryAta: treasuryAta.address,
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("MintNotWithdrawOnly");
+    }
+
+    await gatewayProgram.methods
+      .unwhitelistSplMint([], 0, [], new anchor.BN(0))
+      .accounts({
+        whitelistCandidate: vaultMint.publicKey,
+      })
+      .rpc();
+
+    await gatewayProgram.methods
+      .closeMintVault()
+      .accounts({
+        mintAccount: vaultMint.publicKey,
+        treasuryAta: treasuryAta.address,
+        tokenProgram: spl.TOKEN_PROGRAM_ID,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    expect(await conn.getAccountInfo(pdaAta)).to.be.null;
+    const treasuryAccount = await spl.getAccount(
+      conn,
+      treasuryAta.address,
+      "confirmed"
+    );
+    expect(Number(treasuryAccount.amount)).to.eq(1_000_000);
+  });
+
+  it("Update whitelist entry enforces per-mint deposit settings", async () => {
+    await gatewayProgram.methods
+      .updateWhitelistEntry(
+        new anchor.BN(2_000_000),
+        new anchor.BN(5_000_000),
+        false,
+        null
+      )
+      .accounts({ mintAccount: mint.publicKey })
+      .rpc();
+
+    try {
+      await depositSplTokens(gatewayProgram, conn, wallet, mint, address);
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("DepositBelowMinimum");
     }
-  });
 
-  it("Call with empty address receiver should fail", async () => {
+    await gatewayProgram.methods
+      .updateWhitelistEntry(
+        new anchor.BN(0),
+        new anchor.BN("18446744073709551615"),
+        true,
+        null
+      )
+      .accounts({ mintAccount: mint.publicKey })
+      .rpc();
+
     try {
-      await gatewayProgram.methods
-        .call(Array(20).fill(0), Buffer.from("hello", "utf-8"), revertOptions)
-        .rpc();
+      await depositSplTokens(gatewayProgram, conn, wallet, mint, address);
       throw new Error("Expected error not thrown");
     } catch (err) {
       expect(err).to.be.instanceof(anchor.AnchorError);
-      expect(err.message).to.include("EmptyReceiver");
+      expect(err.message).to.include("DepositPaused");
     }
+
+    await gatewayProgram.methods
+      .updateWhitelistEntry(
+        new anchor.BN(0),
+        new anchor.BN("18446744073709551615"),
+        false,
+        null
+      )
+      .accounts({ mintAccount: mint.publicKey })
+      .rpc();
+    await depositSplTokens(gatewayProgram, conn, wallet, mint, address);
   });
 
-  it("Call with above max payload size should fail", async () => {
+  it("Update whitelist entry fails if minimum is above maximum", async () => {
     try {
       await gatewayProgram.methods
-        .call(
-          Array.from(address),
-          Buffer.from(Array(maxPayloadSize + 1).fill(1)),
-          revertOptions
+        .updateWhitelistEntry(
+          new anchor.BN(2),
+          new anchor.BN(1),
+          false,
+          null
         )
+        .accounts({ mintAccount: mint.publicKey })
         .rpc();
       throw new Error("Expected error not thrown");
     } catch (err) {
       expect(err).to.be.instanceof(anchor.AnchorError);
-      expect(err.message).to.include("MemoLengthExceeded");
+      expect(err.message).to.include("InvalidWhitelistConfig");
     }
   });
 
-  it("Call with max payload size", async () => {
-    const txsig = await gatewayProgram.methods
-      .call(
-        Array.from(address),
-        Buffer.from(Array(maxPayloadSize).fill(1)),
-        revertOptions
-      )
-      .preInstructions([
-        ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 }),
-      ])
-      .rpc({ commitment: "processed" });
-    await conn.getParsedTransaction(txsig, "confirmed");
-  });
-
-  it("Deposit and call with max payload size", async () => {
-    const bal1 = await conn.getBalance(pdaAccount);
-    const txsig = await gatewayProgram.methods
-      .depositAndCall(
-        new anchor.BN(1_000_000_000),
-        Array.from(address),
-        Buffer.from(Array(maxPayloadSize).fill(1)),
-        revertOptions
-      )
-      .preInstructions([
-        ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 }),
-      ])
-      .rpc({ commitment: "processed" });
-    await conn.getParsedTransaction(txsig, "confirmed");
-    const bal2 = await conn.getBalance(pdaAccount);
-    expect(bal2 - bal1).to.be.gte(1_000_000_000);
-  });
-
-  it("Deposit and call", async () => {
-    let bal1 = await conn.getBalance(pdaAccount);
-    const txsig = await gatewayProgram.methods
-      .depositAndCall(
-        new anchor.BN(1_000_000_000),
-        Array.from(address),
-        Buffer.from("hello", "utf-8"),
-        revertOptions
-      )
-      .rpc({ commitment: "processed" });
-    await conn.getParsedTransaction(txsig, "confirmed");
-    let bal2 = await conn.getBalance(pdaAccount);
-    expect(bal2 - bal1).to.be.gte(1_000_000_000);
-  });
+  it("Withdraw SPL token fails if decimals do not match the whitelist entry", async () => {
+    const pdaAta = spl.getAssociatedTokenAddressSync(
+      mint.publicKey,
+      pdaAccount,
+      true
+    );
+    const walletAta = spl.getAssociatedTokenAddressSync(
+      mint.publicKey,
+      wallet.publicKey
+    );
+    const { nonce } = await gatewayProgram.account.pda.fetch(pdaAccount);
 
-  it("Deposit SPL with empty address receiver should fail", async () => {
     try {
-      await depositSplTokens(
-        gatewayProgram,
-        conn,
-        wallet,
+      await withdrawSplToken(
         mint,
-        Buffer.alloc(20)
+        usdcDecimals + 1,
+        new anchor.BN(1_000),
+        nonce,
+        pdaAta,
+        walletAta,
+        wallet.publicKey,
+        gatewayProgram
       );
       throw new Error("Expected error not thrown");
     } catch (err) {
       expect(err).to.be.instanceof(anchor.AnchorError);
-      expect(err.message).to.include("EmptyReceiver");
-    }
-  });
-
-  it("Unwhitelist SPL token and deposit should fail", async () => {
-    await gatewayProgram.methods
-      .unwhitelistSplMint([], 0, [], new anchor.BN(0))
-      .accounts({
-        whitelistCandidate: mint.publicKey,
-      })
-      .rpc();
-
-    try {
-      await depositSplTokens(gatewayProgram, conn, wallet, mint, address);
-      throw new Error("Expected error not thrown");
-    } catch (err) {
-      expect(err).to.be.instanceof(anchor.AnchorError);
-      expect(err.message).to.include("AccountNotInitialized");
+      expect(err.message).to.include("DecimalsMismatch");
     }
   });
 
-  it("Re-whitelist SPL token and deposit should succeed", async () => {
-    await gatewayProgram.methods
-      .whitelistSplMint([], 0, [], new anchor.BN(0))
-      .accounts({
-        whitelistCandidate: mint.publicKey,
-      })
-      .rpc();
-    await depositSplTokens(gatewayProgram, conn, wallet, mint, address);
-  });
-
   it("Unwhitelist SPL token using TSS signature and deposit should fail", async () => {
     const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
     const nonce = pdaAccountData.nonce;
@@ -3075,7 +4967,7 @@ describe("Gateway", () => {
       throw new Error("Expected error not thrown");
     } catch (err) {
       expect(err).to.be.instanceof(anchor.AnchorError);
-      expect(err.message).to.include("AccountNotInitialized");
+      expect(err.message).to.include("MintWithdrawOnly");
     }
   });
 
@@ -3227,18 +5119,103 @@ describe("Gateway", () => {
     }
   });
 
+  it("Withdraw accepts both TSS signatures during rotation overlap", async () => {
+    const newKeyPair = ec.genKeyPair();
+    const newTssAddress = keccak256(
+      Buffer.from(newKeyPair.getPublic(false, "hex").slice(2), "hex")
+    ).slice(-20);
+    const activationSlot = (await conn.getSlot("processed")) + 5;
+    await gatewayProgram.methods
+      .updateTss(
+        Array.from(newTssAddress),
+        new anchor.BN(activationSlot),
+        new anchor.BN(1_000_000)
+      )
+      .rpc();
+    while ((await conn.getSlot("processed")) < activationSlot) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
+    const nonceBefore = (await gatewayProgram.account.pda.fetch(pdaAccount))
+      .nonce;
+    const amount = new anchor.BN(1_000_000);
+    // outbounds signed by the old and the new TSS share the same nonce sequence
+    for (const tssKeyPair of [keyPair, newKeyPair]) {
+      const nonce = (await gatewayProgram.account.pda.fetch(pdaAccount)).nonce;
+      const buffer = Buffer.concat([
+        Buffer.from("ZETACHAIN", "utf-8"),
+        Buffer.from([0x01]),
+        chain_id_bn.toArrayLike(Buffer, "be", 8),
+        nonce.toArrayLike(Buffer, "be", 8),
+        amount.toArrayLike(Buffer, "be", 8),
+        wallet.publicKey.toBuffer(),
+      ]);
+      const message_hash = keccak256(buffer);
+      const { r, s, recoveryParam } = tssKeyPair.sign(message_hash, "hex");
+      const signatureBuffer = Buffer.concat([
+        r.toArrayLike(Buffer, "be", 32),
+        s.toArrayLike(Buffer, "be", 32),
+      ]);
+      await gatewayProgram.methods
+        .withdraw(
+          amount,
+          Array.from(signatureBuffer),
+          Number(recoveryParam),
+          Array.from(message_hash),
+          nonce
+        )
+        .accounts({ recipient: wallet.publicKey })
+        .rpc();
+    }
+
+    const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
+    expect(pdaAccountData.nonce.toNumber()).to.eq(nonceBefore.toNumber() + 2);
+    expect(pdaAccountData.tssAddress).to.be.deep.eq(tssAddress);
+  });
+
   it("Update TSS address", async () => {
     const newTss = new Uint8Array(20);
     randomFillSync(newTss);
-    await gatewayProgram.methods.updateTss(Array.from(newTss)).rpc();
+    const pdaAccountDataBefore = await gatewayProgram.account.pda.fetch(
+      pdaAccount
+    );
+    const activationSlot = (await conn.getSlot()) + 1000;
+    await gatewayProgram.methods
+      .updateTss(
+        Array.from(newTss),
+        new anchor.BN(activationSlot),
+        new anchor.BN(100)
+      )
+      .rpc();
     const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
-    expect(pdaAccountData.tssAddress).to.be.deep.eq(Array.from(newTss));
-    expect(pdaAccountData.nonce.toNumber()).to.eq(0);
+    // current TSS stays in place until the overlap window ends and the nonce carries over
+    expect(pdaAccountData.tssAddress).to.be.deep.eq(
+      pdaAccountDataBefore.tssAddress
+    );
+    expect(pdaAccountData.pendingTssAddress).to.be.deep.eq(Array.from(newTss));
+    expect(pdaAccountData.tssActivationSlot.toNumber()).to.eq(activationSlot);
+    expect(pdaAccountData.tssOverlapEndSlot.toNumber()).to.eq(
+      activationSlot + 100
+    );
+    expect(pdaAccountData.nonce.toNumber()).to.eq(
+      pdaAccountDataBefore.nonce.toNumber()
+    );
+
+    // activation slot in the past is rejected
+    try {
+      await gatewayProgram.methods
+        .updateTss(Array.from(newTss), new anchor.BN(0), new anchor.BN(100))
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("InvalidTssRotation");
+    }
 
     // only the authority stored in PDA can update the TSS address; the following should fail
     try {
       await gatewayProgram.methods
-        .updateTss(Array.from(newTss))
+        .updateTss(Array.from(newTss), new anchor.BN(0), new anchor.BN(0))
         .accounts({
           signer: mint.publicKey,
         })
@@ -3287,7 +5264,11 @@ describe("Gateway", () => {
     // now the old authority cannot update TSS address and will fail
     try {
       await gatewayProgram.methods
-        .updateTss(Array.from(new Uint8Array(20)))
+        .updateTss(
+          Array.from(new Uint8Array(20)),
+          new anchor.BN(0),
+          new anchor.BN(0)
+        )
         .rpc();
       throw new Error("Expected error not thrown");
     } catch (err) {
@@ -3305,4 +5286,158 @@ describe("Gateway", () => {
       expect(err.message).to.include("SignerIsNotAuthority");
     }
   });
+
+  it("Initialize admin config fails if wrong authority", async () => {
+    try {
+      await gatewayProgram.methods
+        .initializeAdminConfig([wallet.publicKey, newAuthority.publicKey], 2)
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("SignerIsNotAuthority");
+    }
+  });
+
+  it("Initialize admin config requires proposals for sensitive operations", async () => {
+    await anchor.web3.sendAndConfirmTransaction(
+      conn,
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: wallet.publicKey,
+          toPubkey: newAuthority.publicKey,
+          lamports: 100_000_000,
+        })
+      ),
+      [wallet]
+    );
+    await gatewayProgram.methods
+      .initializeAdminConfig(
+        [wallet.publicKey, newAuthority.publicKey, random_account.publicKey],
+        2
+      )
+      .accounts({ signer: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+
+    try {
+      await gatewayProgram.methods
+        .resetNonce(new anchor.BN(0))
+        .accounts({ signer: newAuthority.publicKey })
+        .signers([newAuthority])
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("MultisigRequired");
+    }
+  });
+
+  it("Reset nonce through approved admin proposal", async () => {
+    const [proposal] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("admin_proposal", "utf-8"),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      gatewayProgram.programId
+    );
+    await gatewayProgram.methods
+      .proposeAdminAction({ resetNonce: { nonce: new anchor.BN(42) } })
+      .rpc();
+
+    // proposer approval alone is below threshold
+    try {
+      await gatewayProgram.methods
+        .executeAdminAction(new anchor.BN(0))
+        .accounts({ proposer: wallet.publicKey })
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("InsufficientApprovals");
+    }
+
+    try {
+      await gatewayProgram.methods.approveAdminAction(new anchor.BN(0)).rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("ProposalAlreadyApproved");
+    }
+
+    await gatewayProgram.methods
+      .approveAdminAction(new anchor.BN(0))
+      .accounts({ signer: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+    await gatewayProgram.methods
+      .executeAdminAction(new anchor.BN(0))
+      .accounts({ proposer: wallet.publicKey })
+      .rpc();
+
+    const pdaAccountData = await gatewayProgram.account.pda.fetch(pdaAccount);
+    expect(pdaAccountData.nonce.toNumber()).to.equal(42);
+    expect(await conn.getAccountInfo(proposal)).to.be.null;
+  });
+
+  it("Approve admin action fails if not an admin signer", async () => {
+    await gatewayProgram.methods
+      .proposeAdminAction({ resetNonce: { nonce: new anchor.BN(0) } })
+      .rpc();
+
+    const outsider = anchor.web3.Keypair.generate();
+    try {
+      await gatewayProgram.methods
+        .approveAdminAction(new anchor.BN(1))
+        .accounts({ signer: outsider.publicKey })
+        .signers([outsider])
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("SignerIsNotAdmin");
+    }
+  });
+
+  it("Cancel admin action closes the proposal", async () => {
+    const [proposal] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("admin_proposal", "utf-8"),
+        new anchor.BN(1).toArrayLike(Buffer, "le", 8),
+      ],
+      gatewayProgram.programId
+    );
+
+    const outsider = anchor.web3.Keypair.generate();
+    try {
+      await gatewayProgram.methods
+        .cancelAdminAction(new anchor.BN(1))
+        .accounts({ signer: outsider.publicKey, proposer: wallet.publicKey })
+        .signers([outsider])
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("SignerIsNotProposer");
+    }
+
+    // another admin signer cannot cancel the proposal either
+    try {
+      await gatewayProgram.methods
+        .cancelAdminAction(new anchor.BN(1))
+        .accounts({ signer: newAuthority.publicKey, proposer: wallet.publicKey })
+        .signers([newAuthority])
+        .rpc();
+      throw new Error("Expected error not thrown");
+    } catch (err) {
+      expect(err).to.be.instanceof(anchor.AnchorError);
+      expect(err.message).to.include("SignerIsNotProposer");
+    }
+
+    await gatewayProgram.methods
+      .cancelAdminAction(new anchor.BN(1))
+      .accounts({ proposer: wallet.publicKey })
+      .rpc();
+    expect(await conn.getAccountInfo(proposal)).to.be.null;
+  });
 });